@@ -0,0 +1,81 @@
+//! Measures [`Analyzer::run`] on fixture files of about 50, 500 and 5000 lines, and on
+//! static method calls named after each keyword, for 1, 5 and 10 keywords, since every
+//! `walk_in_*` method compares names against each keyword.
+
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use criterion::criterion_group;
+use criterion::criterion_main;
+
+use keyword_impact_analyzer::analyzer::AnalysisOptions;
+use keyword_impact_analyzer::analyzer::Analyzer;
+use keyword_impact_analyzer::files::PackageNames;
+
+const FIXTURES: &[&str] = &[
+    "small.php",
+    "medium.php",
+    "large.php",
+    "static_method_calls.php",
+];
+const KEYWORDS: &[&str] = &[
+    "let", "scope", "async", "await", "using", "defer", "record", "struct", "type", "module",
+];
+const KEYWORD_COUNTS: &[usize] = &[1, 5, 10];
+
+fn analyzer_throughput(c: &mut Criterion) {
+    let fixtures_directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/fixtures");
+    let package_names = PackageNames::load(&fixtures_directory, &[]);
+    let labels: Arc<[String]> = Arc::from([]);
+
+    for fixture in FIXTURES {
+        let path = fixtures_directory.join(fixture);
+        let size = fs::metadata(&path)
+            .unwrap_or_else(|e| panic!("Failed to read fixture {:?}: {}", path, e))
+            .len();
+
+        let mut group = c.benchmark_group(format!("analyzer-run/{}", fixture));
+        group.throughput(Throughput::Bytes(size));
+
+        for &keyword_count in KEYWORD_COUNTS {
+            let keywords: Arc<[String]> = KEYWORDS[..keyword_count]
+                .iter()
+                .map(|keyword| keyword.to_string())
+                .collect();
+
+            group.bench_with_input(
+                BenchmarkId::new("keywords", keyword_count),
+                &path,
+                |b, path| {
+                    let mut arena = Bump::new();
+                    b.iter(|| {
+                        let matches = Analyzer::run(
+                            &arena,
+                            black_box(path),
+                            &package_names,
+                            &keywords,
+                            &labels,
+                            None,
+                            None,
+                            AnalysisOptions::default(),
+                        );
+                        arena.reset();
+
+                        matches
+                    });
+                },
+            );
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, analyzer_throughput);
+criterion_main!(benches);
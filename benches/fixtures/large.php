@@ -0,0 +1,4809 @@
+<?php
+
+declare(strict_types=1);
+
+namespace App\Scope\Async;
+
+use App\Module\Record;
+
+const LET = 'let';
+
+interface Type1
+{
+    public function scope(): array;
+}
+
+enum Defer1: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct1 implements Type1
+{
+    public const ASYNC = 'async1';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 1];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 1) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct1 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer1::Using;
+    }
+}
+
+function let1(Struct1 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 1);
+}
+
+interface Type2
+{
+    public function scope(): array;
+}
+
+enum Defer2: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct2 implements Type2
+{
+    public const ASYNC = 'async2';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 2];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 2) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct2 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer2::Using;
+    }
+}
+
+function let2(Struct2 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 2);
+}
+
+interface Type3
+{
+    public function scope(): array;
+}
+
+enum Defer3: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct3 implements Type3
+{
+    public const ASYNC = 'async3';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 3];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 3) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct3 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer3::Using;
+    }
+}
+
+function let3(Struct3 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 3);
+}
+
+interface Type4
+{
+    public function scope(): array;
+}
+
+enum Defer4: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct4 implements Type4
+{
+    public const ASYNC = 'async4';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 4];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 4) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct4 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer4::Using;
+    }
+}
+
+function let4(Struct4 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 4);
+}
+
+interface Type5
+{
+    public function scope(): array;
+}
+
+enum Defer5: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct5 implements Type5
+{
+    public const ASYNC = 'async5';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 5];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 5) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct5 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer5::Using;
+    }
+}
+
+function let5(Struct5 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 5);
+}
+
+interface Type6
+{
+    public function scope(): array;
+}
+
+enum Defer6: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct6 implements Type6
+{
+    public const ASYNC = 'async6';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 6];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 6) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct6 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer6::Using;
+    }
+}
+
+function let6(Struct6 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 6);
+}
+
+interface Type7
+{
+    public function scope(): array;
+}
+
+enum Defer7: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct7 implements Type7
+{
+    public const ASYNC = 'async7';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 7];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 7) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct7 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer7::Using;
+    }
+}
+
+function let7(Struct7 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 7);
+}
+
+interface Type8
+{
+    public function scope(): array;
+}
+
+enum Defer8: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct8 implements Type8
+{
+    public const ASYNC = 'async8';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 8];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 8) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct8 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer8::Using;
+    }
+}
+
+function let8(Struct8 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 8);
+}
+
+interface Type9
+{
+    public function scope(): array;
+}
+
+enum Defer9: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct9 implements Type9
+{
+    public const ASYNC = 'async9';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 9];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 9) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct9 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer9::Using;
+    }
+}
+
+function let9(Struct9 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 9);
+}
+
+interface Type10
+{
+    public function scope(): array;
+}
+
+enum Defer10: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct10 implements Type10
+{
+    public const ASYNC = 'async10';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 10];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 10) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct10 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer10::Using;
+    }
+}
+
+function let10(Struct10 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 10);
+}
+
+interface Type11
+{
+    public function scope(): array;
+}
+
+enum Defer11: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct11 implements Type11
+{
+    public const ASYNC = 'async11';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 11];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 11) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct11 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer11::Using;
+    }
+}
+
+function let11(Struct11 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 11);
+}
+
+interface Type12
+{
+    public function scope(): array;
+}
+
+enum Defer12: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct12 implements Type12
+{
+    public const ASYNC = 'async12';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 12];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 12) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct12 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer12::Using;
+    }
+}
+
+function let12(Struct12 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 12);
+}
+
+interface Type13
+{
+    public function scope(): array;
+}
+
+enum Defer13: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct13 implements Type13
+{
+    public const ASYNC = 'async13';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 13];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 13) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct13 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer13::Using;
+    }
+}
+
+function let13(Struct13 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 13);
+}
+
+interface Type14
+{
+    public function scope(): array;
+}
+
+enum Defer14: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct14 implements Type14
+{
+    public const ASYNC = 'async14';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 14];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 14) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct14 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer14::Using;
+    }
+}
+
+function let14(Struct14 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 14);
+}
+
+interface Type15
+{
+    public function scope(): array;
+}
+
+enum Defer15: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct15 implements Type15
+{
+    public const ASYNC = 'async15';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 15];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 15) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct15 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer15::Using;
+    }
+}
+
+function let15(Struct15 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 15);
+}
+
+interface Type16
+{
+    public function scope(): array;
+}
+
+enum Defer16: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct16 implements Type16
+{
+    public const ASYNC = 'async16';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 16];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 16) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct16 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer16::Using;
+    }
+}
+
+function let16(Struct16 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 16);
+}
+
+interface Type17
+{
+    public function scope(): array;
+}
+
+enum Defer17: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct17 implements Type17
+{
+    public const ASYNC = 'async17';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 17];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 17) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct17 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer17::Using;
+    }
+}
+
+function let17(Struct17 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 17);
+}
+
+interface Type18
+{
+    public function scope(): array;
+}
+
+enum Defer18: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct18 implements Type18
+{
+    public const ASYNC = 'async18';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 18];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 18) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct18 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer18::Using;
+    }
+}
+
+function let18(Struct18 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 18);
+}
+
+interface Type19
+{
+    public function scope(): array;
+}
+
+enum Defer19: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct19 implements Type19
+{
+    public const ASYNC = 'async19';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 19];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 19) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct19 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer19::Using;
+    }
+}
+
+function let19(Struct19 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 19);
+}
+
+interface Type20
+{
+    public function scope(): array;
+}
+
+enum Defer20: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct20 implements Type20
+{
+    public const ASYNC = 'async20';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 20];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 20) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct20 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer20::Using;
+    }
+}
+
+function let20(Struct20 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 20);
+}
+
+interface Type21
+{
+    public function scope(): array;
+}
+
+enum Defer21: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct21 implements Type21
+{
+    public const ASYNC = 'async21';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 21];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 21) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct21 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer21::Using;
+    }
+}
+
+function let21(Struct21 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 21);
+}
+
+interface Type22
+{
+    public function scope(): array;
+}
+
+enum Defer22: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct22 implements Type22
+{
+    public const ASYNC = 'async22';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 22];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 22) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct22 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer22::Using;
+    }
+}
+
+function let22(Struct22 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 22);
+}
+
+interface Type23
+{
+    public function scope(): array;
+}
+
+enum Defer23: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct23 implements Type23
+{
+    public const ASYNC = 'async23';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 23];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 23) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct23 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer23::Using;
+    }
+}
+
+function let23(Struct23 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 23);
+}
+
+interface Type24
+{
+    public function scope(): array;
+}
+
+enum Defer24: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct24 implements Type24
+{
+    public const ASYNC = 'async24';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 24];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 24) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct24 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer24::Using;
+    }
+}
+
+function let24(Struct24 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 24);
+}
+
+interface Type25
+{
+    public function scope(): array;
+}
+
+enum Defer25: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct25 implements Type25
+{
+    public const ASYNC = 'async25';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 25];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 25) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct25 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer25::Using;
+    }
+}
+
+function let25(Struct25 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 25);
+}
+
+interface Type26
+{
+    public function scope(): array;
+}
+
+enum Defer26: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct26 implements Type26
+{
+    public const ASYNC = 'async26';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 26];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 26) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct26 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer26::Using;
+    }
+}
+
+function let26(Struct26 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 26);
+}
+
+interface Type27
+{
+    public function scope(): array;
+}
+
+enum Defer27: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct27 implements Type27
+{
+    public const ASYNC = 'async27';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 27];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 27) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct27 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer27::Using;
+    }
+}
+
+function let27(Struct27 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 27);
+}
+
+interface Type28
+{
+    public function scope(): array;
+}
+
+enum Defer28: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct28 implements Type28
+{
+    public const ASYNC = 'async28';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 28];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 28) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct28 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer28::Using;
+    }
+}
+
+function let28(Struct28 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 28);
+}
+
+interface Type29
+{
+    public function scope(): array;
+}
+
+enum Defer29: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct29 implements Type29
+{
+    public const ASYNC = 'async29';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 29];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 29) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct29 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer29::Using;
+    }
+}
+
+function let29(Struct29 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 29);
+}
+
+interface Type30
+{
+    public function scope(): array;
+}
+
+enum Defer30: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct30 implements Type30
+{
+    public const ASYNC = 'async30';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 30];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 30) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct30 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer30::Using;
+    }
+}
+
+function let30(Struct30 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 30);
+}
+
+interface Type31
+{
+    public function scope(): array;
+}
+
+enum Defer31: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct31 implements Type31
+{
+    public const ASYNC = 'async31';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 31];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 31) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct31 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer31::Using;
+    }
+}
+
+function let31(Struct31 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 31);
+}
+
+interface Type32
+{
+    public function scope(): array;
+}
+
+enum Defer32: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct32 implements Type32
+{
+    public const ASYNC = 'async32';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 32];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 32) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct32 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer32::Using;
+    }
+}
+
+function let32(Struct32 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 32);
+}
+
+interface Type33
+{
+    public function scope(): array;
+}
+
+enum Defer33: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct33 implements Type33
+{
+    public const ASYNC = 'async33';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 33];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 33) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct33 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer33::Using;
+    }
+}
+
+function let33(Struct33 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 33);
+}
+
+interface Type34
+{
+    public function scope(): array;
+}
+
+enum Defer34: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct34 implements Type34
+{
+    public const ASYNC = 'async34';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 34];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 34) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct34 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer34::Using;
+    }
+}
+
+function let34(Struct34 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 34);
+}
+
+interface Type35
+{
+    public function scope(): array;
+}
+
+enum Defer35: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct35 implements Type35
+{
+    public const ASYNC = 'async35';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 35];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 35) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct35 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer35::Using;
+    }
+}
+
+function let35(Struct35 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 35);
+}
+
+interface Type36
+{
+    public function scope(): array;
+}
+
+enum Defer36: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct36 implements Type36
+{
+    public const ASYNC = 'async36';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 36];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 36) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct36 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer36::Using;
+    }
+}
+
+function let36(Struct36 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 36);
+}
+
+interface Type37
+{
+    public function scope(): array;
+}
+
+enum Defer37: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct37 implements Type37
+{
+    public const ASYNC = 'async37';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 37];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 37) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct37 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer37::Using;
+    }
+}
+
+function let37(Struct37 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 37);
+}
+
+interface Type38
+{
+    public function scope(): array;
+}
+
+enum Defer38: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct38 implements Type38
+{
+    public const ASYNC = 'async38';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 38];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 38) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct38 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer38::Using;
+    }
+}
+
+function let38(Struct38 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 38);
+}
+
+interface Type39
+{
+    public function scope(): array;
+}
+
+enum Defer39: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct39 implements Type39
+{
+    public const ASYNC = 'async39';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 39];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 39) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct39 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer39::Using;
+    }
+}
+
+function let39(Struct39 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 39);
+}
+
+interface Type40
+{
+    public function scope(): array;
+}
+
+enum Defer40: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct40 implements Type40
+{
+    public const ASYNC = 'async40';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 40];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 40) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct40 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer40::Using;
+    }
+}
+
+function let40(Struct40 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 40);
+}
+
+interface Type41
+{
+    public function scope(): array;
+}
+
+enum Defer41: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct41 implements Type41
+{
+    public const ASYNC = 'async41';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 41];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 41) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct41 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer41::Using;
+    }
+}
+
+function let41(Struct41 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 41);
+}
+
+interface Type42
+{
+    public function scope(): array;
+}
+
+enum Defer42: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct42 implements Type42
+{
+    public const ASYNC = 'async42';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 42];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 42) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct42 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer42::Using;
+    }
+}
+
+function let42(Struct42 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 42);
+}
+
+interface Type43
+{
+    public function scope(): array;
+}
+
+enum Defer43: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct43 implements Type43
+{
+    public const ASYNC = 'async43';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 43];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 43) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct43 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer43::Using;
+    }
+}
+
+function let43(Struct43 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 43);
+}
+
+interface Type44
+{
+    public function scope(): array;
+}
+
+enum Defer44: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct44 implements Type44
+{
+    public const ASYNC = 'async44';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 44];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 44) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct44 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer44::Using;
+    }
+}
+
+function let44(Struct44 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 44);
+}
+
+interface Type45
+{
+    public function scope(): array;
+}
+
+enum Defer45: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct45 implements Type45
+{
+    public const ASYNC = 'async45';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 45];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 45) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct45 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer45::Using;
+    }
+}
+
+function let45(Struct45 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 45);
+}
+
+interface Type46
+{
+    public function scope(): array;
+}
+
+enum Defer46: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct46 implements Type46
+{
+    public const ASYNC = 'async46';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 46];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 46) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct46 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer46::Using;
+    }
+}
+
+function let46(Struct46 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 46);
+}
+
+interface Type47
+{
+    public function scope(): array;
+}
+
+enum Defer47: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct47 implements Type47
+{
+    public const ASYNC = 'async47';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 47];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 47) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct47 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer47::Using;
+    }
+}
+
+function let47(Struct47 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 47);
+}
+
+interface Type48
+{
+    public function scope(): array;
+}
+
+enum Defer48: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct48 implements Type48
+{
+    public const ASYNC = 'async48';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 48];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 48) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct48 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer48::Using;
+    }
+}
+
+function let48(Struct48 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 48);
+}
+
+interface Type49
+{
+    public function scope(): array;
+}
+
+enum Defer49: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct49 implements Type49
+{
+    public const ASYNC = 'async49';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 49];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 49) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct49 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer49::Using;
+    }
+}
+
+function let49(Struct49 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 49);
+}
+
+interface Type50
+{
+    public function scope(): array;
+}
+
+enum Defer50: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct50 implements Type50
+{
+    public const ASYNC = 'async50';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 50];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 50) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct50 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer50::Using;
+    }
+}
+
+function let50(Struct50 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 50);
+}
+
+interface Type51
+{
+    public function scope(): array;
+}
+
+enum Defer51: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct51 implements Type51
+{
+    public const ASYNC = 'async51';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 51];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 51) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct51 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer51::Using;
+    }
+}
+
+function let51(Struct51 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 51);
+}
+
+interface Type52
+{
+    public function scope(): array;
+}
+
+enum Defer52: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct52 implements Type52
+{
+    public const ASYNC = 'async52';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 52];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 52) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct52 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer52::Using;
+    }
+}
+
+function let52(Struct52 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 52);
+}
+
+interface Type53
+{
+    public function scope(): array;
+}
+
+enum Defer53: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct53 implements Type53
+{
+    public const ASYNC = 'async53';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 53];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 53) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct53 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer53::Using;
+    }
+}
+
+function let53(Struct53 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 53);
+}
+
+interface Type54
+{
+    public function scope(): array;
+}
+
+enum Defer54: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct54 implements Type54
+{
+    public const ASYNC = 'async54';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 54];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 54) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct54 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer54::Using;
+    }
+}
+
+function let54(Struct54 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 54);
+}
+
+interface Type55
+{
+    public function scope(): array;
+}
+
+enum Defer55: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct55 implements Type55
+{
+    public const ASYNC = 'async55';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 55];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 55) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct55 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer55::Using;
+    }
+}
+
+function let55(Struct55 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 55);
+}
+
+interface Type56
+{
+    public function scope(): array;
+}
+
+enum Defer56: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct56 implements Type56
+{
+    public const ASYNC = 'async56';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 56];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 56) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct56 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer56::Using;
+    }
+}
+
+function let56(Struct56 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 56);
+}
+
+interface Type57
+{
+    public function scope(): array;
+}
+
+enum Defer57: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct57 implements Type57
+{
+    public const ASYNC = 'async57';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 57];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 57) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct57 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer57::Using;
+    }
+}
+
+function let57(Struct57 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 57);
+}
+
+interface Type58
+{
+    public function scope(): array;
+}
+
+enum Defer58: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct58 implements Type58
+{
+    public const ASYNC = 'async58';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 58];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 58) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct58 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer58::Using;
+    }
+}
+
+function let58(Struct58 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 58);
+}
+
+interface Type59
+{
+    public function scope(): array;
+}
+
+enum Defer59: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct59 implements Type59
+{
+    public const ASYNC = 'async59';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 59];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 59) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct59 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer59::Using;
+    }
+}
+
+function let59(Struct59 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 59);
+}
+
+interface Type60
+{
+    public function scope(): array;
+}
+
+enum Defer60: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct60 implements Type60
+{
+    public const ASYNC = 'async60';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 60];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 60) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct60 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer60::Using;
+    }
+}
+
+function let60(Struct60 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 60);
+}
+
+interface Type61
+{
+    public function scope(): array;
+}
+
+enum Defer61: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct61 implements Type61
+{
+    public const ASYNC = 'async61';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 61];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 61) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct61 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer61::Using;
+    }
+}
+
+function let61(Struct61 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 61);
+}
+
+interface Type62
+{
+    public function scope(): array;
+}
+
+enum Defer62: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct62 implements Type62
+{
+    public const ASYNC = 'async62';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 62];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 62) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct62 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer62::Using;
+    }
+}
+
+function let62(Struct62 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 62);
+}
+
+interface Type63
+{
+    public function scope(): array;
+}
+
+enum Defer63: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct63 implements Type63
+{
+    public const ASYNC = 'async63';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 63];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 63) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct63 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer63::Using;
+    }
+}
+
+function let63(Struct63 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 63);
+}
+
+interface Type64
+{
+    public function scope(): array;
+}
+
+enum Defer64: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct64 implements Type64
+{
+    public const ASYNC = 'async64';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 64];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 64) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct64 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer64::Using;
+    }
+}
+
+function let64(Struct64 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 64);
+}
+
+interface Type65
+{
+    public function scope(): array;
+}
+
+enum Defer65: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct65 implements Type65
+{
+    public const ASYNC = 'async65';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 65];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 65) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct65 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer65::Using;
+    }
+}
+
+function let65(Struct65 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 65);
+}
+
+interface Type66
+{
+    public function scope(): array;
+}
+
+enum Defer66: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct66 implements Type66
+{
+    public const ASYNC = 'async66';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 66];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 66) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct66 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer66::Using;
+    }
+}
+
+function let66(Struct66 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 66);
+}
+
+interface Type67
+{
+    public function scope(): array;
+}
+
+enum Defer67: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct67 implements Type67
+{
+    public const ASYNC = 'async67';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 67];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 67) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct67 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer67::Using;
+    }
+}
+
+function let67(Struct67 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 67);
+}
+
+interface Type68
+{
+    public function scope(): array;
+}
+
+enum Defer68: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct68 implements Type68
+{
+    public const ASYNC = 'async68';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 68];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 68) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct68 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer68::Using;
+    }
+}
+
+function let68(Struct68 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 68);
+}
+
+interface Type69
+{
+    public function scope(): array;
+}
+
+enum Defer69: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct69 implements Type69
+{
+    public const ASYNC = 'async69';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 69];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 69) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct69 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer69::Using;
+    }
+}
+
+function let69(Struct69 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 69);
+}
+
+interface Type70
+{
+    public function scope(): array;
+}
+
+enum Defer70: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct70 implements Type70
+{
+    public const ASYNC = 'async70';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 70];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 70) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct70 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer70::Using;
+    }
+}
+
+function let70(Struct70 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 70);
+}
+
+interface Type71
+{
+    public function scope(): array;
+}
+
+enum Defer71: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct71 implements Type71
+{
+    public const ASYNC = 'async71';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 71];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 71) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct71 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer71::Using;
+    }
+}
+
+function let71(Struct71 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 71);
+}
+
+interface Type72
+{
+    public function scope(): array;
+}
+
+enum Defer72: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct72 implements Type72
+{
+    public const ASYNC = 'async72';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 72];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 72) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct72 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer72::Using;
+    }
+}
+
+function let72(Struct72 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 72);
+}
+
+interface Type73
+{
+    public function scope(): array;
+}
+
+enum Defer73: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct73 implements Type73
+{
+    public const ASYNC = 'async73';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 73];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 73) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct73 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer73::Using;
+    }
+}
+
+function let73(Struct73 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 73);
+}
+
+interface Type74
+{
+    public function scope(): array;
+}
+
+enum Defer74: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct74 implements Type74
+{
+    public const ASYNC = 'async74';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 74];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 74) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct74 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer74::Using;
+    }
+}
+
+function let74(Struct74 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 74);
+}
+
+interface Type75
+{
+    public function scope(): array;
+}
+
+enum Defer75: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct75 implements Type75
+{
+    public const ASYNC = 'async75';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 75];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 75) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct75 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer75::Using;
+    }
+}
+
+function let75(Struct75 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 75);
+}
+
+interface Type76
+{
+    public function scope(): array;
+}
+
+enum Defer76: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct76 implements Type76
+{
+    public const ASYNC = 'async76';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 76];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 76) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct76 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer76::Using;
+    }
+}
+
+function let76(Struct76 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 76);
+}
+
+interface Type77
+{
+    public function scope(): array;
+}
+
+enum Defer77: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct77 implements Type77
+{
+    public const ASYNC = 'async77';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 77];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 77) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct77 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer77::Using;
+    }
+}
+
+function let77(Struct77 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 77);
+}
+
+interface Type78
+{
+    public function scope(): array;
+}
+
+enum Defer78: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct78 implements Type78
+{
+    public const ASYNC = 'async78';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 78];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 78) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct78 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer78::Using;
+    }
+}
+
+function let78(Struct78 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 78);
+}
+
+interface Type79
+{
+    public function scope(): array;
+}
+
+enum Defer79: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct79 implements Type79
+{
+    public const ASYNC = 'async79';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 79];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 79) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct79 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer79::Using;
+    }
+}
+
+function let79(Struct79 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 79);
+}
+
+interface Type80
+{
+    public function scope(): array;
+}
+
+enum Defer80: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct80 implements Type80
+{
+    public const ASYNC = 'async80';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 80];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 80) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct80 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer80::Using;
+    }
+}
+
+function let80(Struct80 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 80);
+}
+
+interface Type81
+{
+    public function scope(): array;
+}
+
+enum Defer81: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct81 implements Type81
+{
+    public const ASYNC = 'async81';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 81];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 81) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct81 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer81::Using;
+    }
+}
+
+function let81(Struct81 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 81);
+}
+
+interface Type82
+{
+    public function scope(): array;
+}
+
+enum Defer82: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct82 implements Type82
+{
+    public const ASYNC = 'async82';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 82];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 82) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct82 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer82::Using;
+    }
+}
+
+function let82(Struct82 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 82);
+}
+
+interface Type83
+{
+    public function scope(): array;
+}
+
+enum Defer83: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct83 implements Type83
+{
+    public const ASYNC = 'async83';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 83];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 83) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct83 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer83::Using;
+    }
+}
+
+function let83(Struct83 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 83);
+}
+
+interface Type84
+{
+    public function scope(): array;
+}
+
+enum Defer84: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct84 implements Type84
+{
+    public const ASYNC = 'async84';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 84];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 84) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct84 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer84::Using;
+    }
+}
+
+function let84(Struct84 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 84);
+}
+
+interface Type85
+{
+    public function scope(): array;
+}
+
+enum Defer85: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct85 implements Type85
+{
+    public const ASYNC = 'async85';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 85];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 85) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct85 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer85::Using;
+    }
+}
+
+function let85(Struct85 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 85);
+}
+
+interface Type86
+{
+    public function scope(): array;
+}
+
+enum Defer86: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct86 implements Type86
+{
+    public const ASYNC = 'async86';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 86];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 86) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct86 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer86::Using;
+    }
+}
+
+function let86(Struct86 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 86);
+}
+
+interface Type87
+{
+    public function scope(): array;
+}
+
+enum Defer87: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct87 implements Type87
+{
+    public const ASYNC = 'async87';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 87];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 87) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct87 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer87::Using;
+    }
+}
+
+function let87(Struct87 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 87);
+}
+
+interface Type88
+{
+    public function scope(): array;
+}
+
+enum Defer88: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct88 implements Type88
+{
+    public const ASYNC = 'async88';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 88];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 88) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct88 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer88::Using;
+    }
+}
+
+function let88(Struct88 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 88);
+}
+
+interface Type89
+{
+    public function scope(): array;
+}
+
+enum Defer89: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct89 implements Type89
+{
+    public const ASYNC = 'async89';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 89];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 89) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct89 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer89::Using;
+    }
+}
+
+function let89(Struct89 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 89);
+}
+
+interface Type90
+{
+    public function scope(): array;
+}
+
+enum Defer90: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct90 implements Type90
+{
+    public const ASYNC = 'async90';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 90];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 90) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct90 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer90::Using;
+    }
+}
+
+function let90(Struct90 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 90);
+}
+
+interface Type91
+{
+    public function scope(): array;
+}
+
+enum Defer91: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct91 implements Type91
+{
+    public const ASYNC = 'async91';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 91];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 91) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct91 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer91::Using;
+    }
+}
+
+function let91(Struct91 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 91);
+}
+
+interface Type92
+{
+    public function scope(): array;
+}
+
+enum Defer92: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct92 implements Type92
+{
+    public const ASYNC = 'async92';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 92];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 92) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct92 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer92::Using;
+    }
+}
+
+function let92(Struct92 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 92);
+}
+
+interface Type93
+{
+    public function scope(): array;
+}
+
+enum Defer93: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct93 implements Type93
+{
+    public const ASYNC = 'async93';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 93];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 93) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct93 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer93::Using;
+    }
+}
+
+function let93(Struct93 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 93);
+}
+
+interface Type94
+{
+    public function scope(): array;
+}
+
+enum Defer94: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct94 implements Type94
+{
+    public const ASYNC = 'async94';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 94];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 94) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct94 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer94::Using;
+    }
+}
+
+function let94(Struct94 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 94);
+}
+
+interface Type95
+{
+    public function scope(): array;
+}
+
+enum Defer95: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct95 implements Type95
+{
+    public const ASYNC = 'async95';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 95];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 95) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct95 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer95::Using;
+    }
+}
+
+function let95(Struct95 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 95);
+}
+
+interface Type96
+{
+    public function scope(): array;
+}
+
+enum Defer96: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct96 implements Type96
+{
+    public const ASYNC = 'async96';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 96];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 96) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct96 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer96::Using;
+    }
+}
+
+function let96(Struct96 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 96);
+}
+
+interface Type97
+{
+    public function scope(): array;
+}
+
+enum Defer97: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct97 implements Type97
+{
+    public const ASYNC = 'async97';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 97];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 97) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct97 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer97::Using;
+    }
+}
+
+function let97(Struct97 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 97);
+}
+
+interface Type98
+{
+    public function scope(): array;
+}
+
+enum Defer98: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct98 implements Type98
+{
+    public const ASYNC = 'async98';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 98];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 98) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct98 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer98::Using;
+    }
+}
+
+function let98(Struct98 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 98);
+}
+
+interface Type99
+{
+    public function scope(): array;
+}
+
+enum Defer99: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct99 implements Type99
+{
+    public const ASYNC = 'async99';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 99];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 99) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct99 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer99::Using;
+    }
+}
+
+function let99(Struct99 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 99);
+}
+
+interface Type100
+{
+    public function scope(): array;
+}
+
+enum Defer100: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct100 implements Type100
+{
+    public const ASYNC = 'async100';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 100];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 100) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct100 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer100::Using;
+    }
+}
+
+function let100(Struct100 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 100);
+}
@@ -0,0 +1,489 @@
+<?php
+
+declare(strict_types=1);
+
+namespace App\Scope\Async;
+
+use App\Module\Record;
+
+const LET = 'let';
+
+interface Type1
+{
+    public function scope(): array;
+}
+
+enum Defer1: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct1 implements Type1
+{
+    public const ASYNC = 'async1';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 1];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 1) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct1 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer1::Using;
+    }
+}
+
+function let1(Struct1 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 1);
+}
+
+interface Type2
+{
+    public function scope(): array;
+}
+
+enum Defer2: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct2 implements Type2
+{
+    public const ASYNC = 'async2';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 2];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 2) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct2 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer2::Using;
+    }
+}
+
+function let2(Struct2 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 2);
+}
+
+interface Type3
+{
+    public function scope(): array;
+}
+
+enum Defer3: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct3 implements Type3
+{
+    public const ASYNC = 'async3';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 3];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 3) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct3 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer3::Using;
+    }
+}
+
+function let3(Struct3 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 3);
+}
+
+interface Type4
+{
+    public function scope(): array;
+}
+
+enum Defer4: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct4 implements Type4
+{
+    public const ASYNC = 'async4';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 4];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 4) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct4 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer4::Using;
+    }
+}
+
+function let4(Struct4 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 4);
+}
+
+interface Type5
+{
+    public function scope(): array;
+}
+
+enum Defer5: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct5 implements Type5
+{
+    public const ASYNC = 'async5';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 5];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 5) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct5 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer5::Using;
+    }
+}
+
+function let5(Struct5 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 5);
+}
+
+interface Type6
+{
+    public function scope(): array;
+}
+
+enum Defer6: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct6 implements Type6
+{
+    public const ASYNC = 'async6';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 6];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 6) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct6 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer6::Using;
+    }
+}
+
+function let6(Struct6 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 6);
+}
+
+interface Type7
+{
+    public function scope(): array;
+}
+
+enum Defer7: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct7 implements Type7
+{
+    public const ASYNC = 'async7';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 7];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 7) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct7 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer7::Using;
+    }
+}
+
+function let7(Struct7 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 7);
+}
+
+interface Type8
+{
+    public function scope(): array;
+}
+
+enum Defer8: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct8 implements Type8
+{
+    public const ASYNC = 'async8';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 8];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 8) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct8 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer8::Using;
+    }
+}
+
+function let8(Struct8 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 8);
+}
+
+interface Type9
+{
+    public function scope(): array;
+}
+
+enum Defer9: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct9 implements Type9
+{
+    public const ASYNC = 'async9';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 9];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 9) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct9 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer9::Using;
+    }
+}
+
+function let9(Struct9 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 9);
+}
+
+interface Type10
+{
+    public function scope(): array;
+}
+
+enum Defer10: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct10 implements Type10
+{
+    public const ASYNC = 'async10';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC, 'index' => 10];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 10) {
+            goto defer;
+        }
+
+        $result = $callback(scope: $this->scope(), record: $this->record);
+        $message = <<<RECORD
+            Struct10 awaited {$using} times
+            RECORD;
+
+        return [$result, $message];
+
+        defer:
+        return Defer10::Using;
+    }
+}
+
+function let10(Struct10 $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 10);
+}
@@ -0,0 +1,52 @@
+<?php
+
+declare(strict_types=1);
+
+namespace App\Scope\Async;
+
+use App\Module\Record;
+
+const LET = 'let';
+
+interface Type
+{
+    public function scope(): array;
+}
+
+enum Defer: string
+{
+    case Using = 'using';
+    case Await = 'await';
+}
+
+final class Struct implements Type
+{
+    public const ASYNC = 'async';
+
+    public function __construct(
+        private readonly array $record = [],
+    ) {
+    }
+
+    public function scope(): array
+    {
+        return ['let' => LET, 'module' => self::ASYNC];
+    }
+
+    public function await(callable $callback, int $using = 0): mixed
+    {
+        if ($using > 3) {
+            goto defer;
+        }
+
+        return $callback(scope: $this->scope(), record: $this->record);
+
+        defer:
+        return Defer::Using;
+    }
+}
+
+function let(Struct $struct): mixed
+{
+    return $struct->await(static fn (...$args) => $args, using: 1);
+}
@@ -0,0 +1,30 @@
+<?php
+
+declare(strict_types=1);
+
+namespace App\Calls;
+
+use App\Factory;
+
+final class StaticCalls
+{
+    public function run(): array
+    {
+        return [
+            Factory::let(),
+            Factory::scope(),
+            Factory::async(),
+            Factory::await(),
+            Factory::using(),
+            Factory::defer(),
+            Factory::record(),
+            Factory::struct(),
+            Factory::type(),
+            Factory::module(),
+            self::let(),
+            static::scope(),
+            parent::async(),
+            \App\Factory::await(),
+        ];
+    }
+}
@@ -1,9 +1,18 @@
-use std::path::{Path, PathBuf};
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
 
 use anyhow::Result;
 use bumpalo::Bump;
 use rayon::prelude::*;
 
+use mago_database::file::File;
+use mago_database::file::FileType;
 use mago_names::ResolvedNames;
 use mago_names::resolver::NameResolver;
 use mago_span::HasPosition;
@@ -11,126 +20,1065 @@ use mago_syntax::ast::*;
 use mago_syntax::parser::parse_file;
 use mago_syntax::walker::Walker;
 
-use crate::files::{read_file, walk_files};
-use crate::results::{AnalysisReport, KeywordMatch, LabelMatch, Vendor};
+use crate::cache::CachedMatches;
+use crate::cache::ParseCache;
+use crate::files::PackageNames;
+use crate::files::PathFilter;
+use crate::files::VendorFilter;
+use crate::files::is_test_file;
+use crate::files::read_file;
+use crate::files::walk_files;
+use crate::match_store::MatchStore;
+use crate::match_store::filter_matches;
+use crate::progress;
+use crate::results::AnalysisReport;
+use crate::results::CustomVendor;
+use crate::results::FileMatches;
+use crate::results::KeywordMatch;
+use crate::results::LabelMatch;
+use crate::results::MatchKind;
+use crate::results::SegregatedAnalysisReport;
+use crate::results::Vendor;
 
+/// What the `analyze_directory*` functions analyze, and how.
+#[derive(Debug, Clone)]
+pub struct DirectoryAnalysis<'a> {
+    /// Holds one `<vendor>/<package>` directory per package.
+    pub sources_directory: PathBuf,
+    /// Parse results are reused from this directory when given.
+    pub cache_directory: Option<PathBuf>,
+    pub keywords: Vec<String>,
+    pub labels: Vec<String>,
+    pub vendor_filter: VendorFilter,
+    /// Packages are attributed to these by prefix in addition to the built-in vendors.
+    pub custom_vendors: &'a [CustomVendor],
+    pub path_filter: PathFilter,
+    pub options: AnalysisOptions,
+    /// Every identifier is recorded here when given.
+    pub match_store: Option<&'a MatchStore>,
+}
+
+impl DirectoryAnalysis<'_> {
+    /// Analyzes every PHP file below `sources_directory` for `keywords`, without
+    /// labels, filters, cache or match store.
+    pub fn new(sources_directory: PathBuf, keywords: Vec<String>) -> Self {
+        Self {
+            sources_directory,
+            cache_directory: None,
+            keywords,
+            labels: Vec::new(),
+            vendor_filter: VendorFilter::default(),
+            custom_vendors: &[],
+            path_filter: PathFilter::default(),
+            options: AnalysisOptions::default(),
+            match_store: None,
+        }
+    }
+}
+
+/// Analyzes every PHP file below the sources directory of `analysis` for uses of
+/// its keywords and labels.
 #[tracing::instrument(name = "analyzing-directory")]
-pub fn analyze_directory(
-    sources_directory: PathBuf,
-    keywords: Vec<String>,
-    labels: Vec<String>,
-) -> Result<AnalysisReport> {
+pub fn analyze_directory(analysis: DirectoryAnalysis<'_>) -> Result<AnalysisReport> {
     tracing::info!("Starting analysis...");
 
-    let sources_canonical = sources_directory.canonicalize()?;
+    let prepared = prepare(&analysis)?;
+
+    let report = analyze_files(
+        prepared.files,
+        &prepared.package_names,
+        &analysis.keywords,
+        &analysis.labels,
+        prepared.cache.as_ref(),
+        analysis.match_store,
+        analysis.options,
+    );
+
+    tracing::info!("Analysis complete.");
+
+    Ok(report)
+}
+
+/// Like [`analyze_directory`], but passes the keyword matches of each file to `on_file`
+/// as soon as the file is analyzed instead of building a report, so that memory use does
+/// not grow with the number of matches. Returns the number of analyzed files.
+///
+/// `on_file` is called from a single thread, with the files in no particular order.
+#[tracing::instrument(name = "analyzing-directory", skip(on_file))]
+pub fn analyze_directory_streaming<F>(analysis: DirectoryAnalysis<'_>, on_file: F) -> Result<usize>
+where
+    F: Fn(Vec<KeywordMatch>) + Sync + Send,
+{
+    tracing::info!("Starting streaming analysis...");
+
+    let prepared = prepare(&analysis)?;
+
+    let total_files = analyze_files_streaming(
+        prepared.files,
+        &prepared.package_names,
+        &analysis.keywords,
+        &analysis.labels,
+        prepared.cache.as_ref(),
+        analysis.match_store,
+        analysis.options,
+        |matches| on_file(matches.keyword_matches),
+    );
+
+    tracing::info!("Analysis complete.");
+
+    Ok(total_files)
+}
+
+/// Like [`analyze_directory`], but analyzes each batch of `vendor/package` directories
+/// as soon as it arrives, e.g. while later packages are still being downloaded, and
+/// then the packages below the sources directory that were in no batch.
+///
+/// `batches` may block until the next batch is ready; the report grows with each one.
+#[tracing::instrument(name = "analyzing-directory-pipelined", skip(batches))]
+pub fn analyze_directory_pipelined(
+    analysis: DirectoryAnalysis<'_>,
+    batches: impl Iterator<Item = Vec<String>>,
+) -> Result<AnalysisReport> {
+    tracing::info!("Starting pipelined analysis...");
 
-    let keyword_refs: Vec<&str> = keywords.iter().map(|s| s.as_str()).collect();
-    let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
-    let all_matches: Vec<(Vec<KeywordMatch>, Vec<LabelMatch>)> = walk_files(&sources_canonical)
-        .map_init(Bump::new, |arena, file| {
-            Analyzer::run(arena, &file, &sources_canonical, &keyword_refs, &label_refs)
+    let PreparedAnalysis {
+        sources_canonical,
+        cache,
+        mut package_names,
+        files: earlier_files,
+    } = prepare(&analysis)?;
+
+    let mut report = AnalysisReport::new(0, BTreeSet::new());
+    let mut batched_packages = HashSet::new();
+    for batch in batches {
+        tracing::debug!("Analyzing a batch of {} packages", batch.len());
+        package_names.load_packages(&batch);
+
+        let files = collect_files(
+            &sources_canonical,
+            &package_names,
+            analysis.vendor_filter.clone(),
+            analysis.path_filter.clone().only_packages(&batch),
+            analysis.options,
+        );
+        report.merge(analyze_files(
+            files,
+            &package_names,
+            &analysis.keywords,
+            &analysis.labels,
+            cache.as_ref(),
+            analysis.match_store,
+            analysis.options,
+        ));
+
+        batched_packages.extend(batch.iter().map(|package| package.to_lowercase()));
+    }
+
+    // Packages extracted by earlier runs are not part of any batch.
+    let files = earlier_files
+        .into_iter()
+        .filter(|file| {
+            package_names
+                .directory_of(file)
+                .is_none_or(|directory| !batched_packages.contains(&directory.to_lowercase()))
         })
         .collect();
+    report.merge(analyze_files(
+        files,
+        &package_names,
+        &analysis.keywords,
+        &analysis.labels,
+        cache.as_ref(),
+        analysis.match_store,
+        analysis.options,
+    ));
+
+    tracing::info!("Analysis complete.");
+
+    Ok(report)
+}
 
-    tracing::info!("Collected matches from {} files.", all_matches.len());
+/// Like [`analyze_directory`], but reports production and test files separately.
+#[tracing::instrument(name = "analyzing-directory-segregated")]
+pub fn analyze_directory_segregated(
+    analysis: DirectoryAnalysis<'_>,
+) -> Result<SegregatedAnalysisReport> {
+    tracing::info!("Starting segregated analysis...");
 
-    let mut report = AnalysisReport::new(all_matches.len());
-    let mut keyword_matches = Vec::new();
-    let mut label_matches = Vec::new();
-    for (keywords, labels) in all_matches {
-        keyword_matches.extend(keywords);
-        label_matches.extend(labels);
-    }
+    let prepared = prepare(&analysis)?;
+
+    let (test_files, production_files): (Vec<PathBuf>, Vec<PathBuf>) =
+        prepared.files.into_par_iter().partition(|file| {
+            is_test_file(
+                file.strip_prefix(&prepared.sources_canonical)
+                    .unwrap_or(file),
+            )
+        });
 
-    report.add_keyword_matches(keyword_matches);
-    report.add_label_matches(label_matches);
+    tracing::info!(
+        "Found {} production files and {} test files.",
+        production_files.len(),
+        test_files.len()
+    );
 
-    report.ensure_all_keywords(&keywords);
+    let production_results = analyze_files(
+        production_files,
+        &prepared.package_names,
+        &analysis.keywords,
+        &analysis.labels,
+        prepared.cache.as_ref(),
+        analysis.match_store,
+        analysis.options,
+    );
+    let test_results = analyze_files(
+        test_files,
+        &prepared.package_names,
+        &analysis.keywords,
+        &analysis.labels,
+        prepared.cache.as_ref(),
+        analysis.match_store,
+        analysis.options,
+    );
 
     tracing::info!("Analysis complete.");
 
-    Ok(report)
+    Ok(SegregatedAnalysisReport {
+        production_results,
+        test_results,
+    })
+}
+
+/// What the `analyze_directory*` functions share before analyzing any file.
+struct PreparedAnalysis {
+    sources_canonical: PathBuf,
+    cache: Option<ParseCache>,
+    package_names: PackageNames,
+    /// The files below the sources directory that pass the filters of the analysis.
+    files: Vec<PathBuf>,
+}
+
+fn prepare(analysis: &DirectoryAnalysis<'_>) -> Result<PreparedAnalysis> {
+    let sources_canonical = analysis.sources_directory.canonicalize()?;
+    let cache = analysis
+        .cache_directory
+        .clone()
+        .map(|directory| {
+            ParseCache::new(
+                directory,
+                &analysis.keywords,
+                &analysis.labels,
+                analysis.options,
+                analysis.custom_vendors,
+            )
+        })
+        .transpose()?;
+    let package_names = PackageNames::load(&sources_canonical, analysis.custom_vendors);
+
+    let files = collect_files(
+        &sources_canonical,
+        &package_names,
+        analysis.vendor_filter.clone(),
+        analysis.path_filter.clone(),
+        analysis.options,
+    );
+
+    Ok(PreparedAnalysis {
+        sources_canonical,
+        cache,
+        package_names,
+        files,
+    })
+}
+
+fn collect_files(
+    sources_canonical: &Path,
+    package_names: &PackageNames,
+    vendor_filter: VendorFilter,
+    path_filter: PathFilter,
+    options: AnalysisOptions,
+) -> Vec<PathBuf> {
+    let (files, walk_errors) = walk_files(sources_canonical, vendor_filter, path_filter);
+    for error in &walk_errors {
+        tracing::warn!("Skipped {:?}: {}", error.path, error.error);
+    }
+
+    if !walk_errors.is_empty() {
+        tracing::warn!(
+            "Skipped {} files or directories that could not be read",
+            walk_errors.len()
+        );
+    }
+
+    if !options.skip_other_vendors {
+        return files.collect();
+    }
+
+    let (files, skipped): (Vec<PathBuf>, Vec<PathBuf>) =
+        files.partition(|file| package_names.vendor_of(file).is_well_known());
+
+    let skipped_packages: HashSet<String> = skipped
+        .iter()
+        .filter_map(|file| package_names.package_of(file))
+        .collect();
+
+    tracing::info!(
+        "Skipped {} packages from vendors that are not well-known",
+        skipped_packages.len()
+    );
+
+    files
+}
+
+fn analyze_files(
+    files: Vec<PathBuf>,
+    package_names: &PackageNames,
+    keywords: &[String],
+    labels: &[String],
+    cache: Option<&ParseCache>,
+    match_store: Option<&MatchStore>,
+    options: AnalysisOptions,
+) -> AnalysisReport {
+    let packages = analyzed_packages(&files, package_names);
+    let mut report = AnalysisReport::new(files.len(), packages);
+    report.set_keep_locations(options.keep_locations);
+
+    // Each file's matches are added as soon as it is analyzed, so the matches of all
+    // files are never held at once.
+    analyze_files_streaming(
+        files,
+        package_names,
+        keywords,
+        labels,
+        cache,
+        match_store,
+        options,
+        |matches| report.add_file_matches(matches),
+    );
+
+    report.ensure_all_keywords(keywords);
+
+    report
+}
+
+fn analyzed_packages(files: &[PathBuf], package_names: &PackageNames) -> BTreeSet<String> {
+    files
+        .par_iter()
+        .filter_map(|file| package_names.package_of(file))
+        .collect()
 }
 
+/// Number of analyzed files whose matches may wait for the collector, so that
+/// workers block instead of buffering matches when it falls behind.
+const STREAMING_BUFFER_SIZE: usize = 1024;
+
+/// Analyzes `files` on the rayon pool and passes the matches of each file to `on_file`
+/// on a single collector thread. Returns the number of analyzed files.
+#[allow(clippy::too_many_arguments)]
+fn analyze_files_streaming<F>(
+    files: Vec<PathBuf>,
+    package_names: &PackageNames,
+    keywords: &[String],
+    labels: &[String],
+    cache: Option<&ParseCache>,
+    match_store: Option<&MatchStore>,
+    options: AnalysisOptions,
+    mut on_file: F,
+) -> usize
+where
+    F: FnMut(FileMatches) + Send,
+{
+    let keyword_list: Arc<[String]> = Arc::from(keywords);
+    let label_list: Arc<[String]> = Arc::from(labels);
+    let total_files = files.len();
+    let progress = progress::start(total_files);
+    let (sender, receiver) = mpsc::sync_channel(STREAMING_BUFFER_SIZE);
+
+    thread::scope(|scope| {
+        let collector = scope.spawn(move || {
+            for matches in receiver {
+                on_file(matches);
+            }
+        });
+
+        files.into_par_iter().for_each_init(
+            || (Bump::new(), sender.clone()),
+            |(arena, sender), file| {
+                let matches = Analyzer::run(
+                    arena,
+                    &file,
+                    package_names,
+                    &keyword_list,
+                    &label_list,
+                    cache,
+                    match_store,
+                    options,
+                );
+                progress::advance(&progress);
+
+                // Only fails if the collector panicked, which `join` reports below.
+                let _ = sender.send(matches);
+            },
+        );
+        // The collector stops once every sender is dropped.
+        drop(sender);
+
+        collector.join().expect("Match collector panicked");
+    });
+
+    tracing::info!("Collected matches from {} files.", total_files);
+
+    total_files
+}
+
+/// Analyzes a single PHP snippet that is not part of any package, such as source read from stdin.
+pub fn analyze_string(
+    source: String,
+    keywords: &[String],
+    labels: &[String],
+    options: AnalysisOptions,
+) -> FileMatches {
+    let arena = Bump::new();
+    let file = File::new(
+        Cow::Borrowed("<stdin>"),
+        FileType::Host,
+        None,
+        Cow::Owned(source),
+    );
+
+    let mut matches = Analyzer::run_on_file(
+        &arena,
+        Vendor::Other,
+        None,
+        &file,
+        &Arc::from(keywords),
+        &Arc::from(labels),
+        options,
+    );
+    if options.dedup_per_file {
+        dedup_per_file(&mut matches.keyword_matches);
+    }
+
+    matches
+}
+
+/// Keeps the first soft and the first hard match of each keyword in a file's matches.
+fn dedup_per_file(keyword_matches: &mut Vec<KeywordMatch>) {
+    let mut seen = HashSet::new();
+    keyword_matches.retain(|m| seen.insert((m.keyword.clone(), m.is_hard)));
+}
+
+/// Optional detections that are off by default because they are noisy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AnalysisOptions {
+    /// Report string literal array keys (`['let' => 1]`) as soft matches.
+    pub analyze_string_keys: bool,
+    /// Report variables (`$let`) as soft matches.
+    pub include_variables: bool,
+    /// Only analyze packages from well-known vendors.
+    pub skip_other_vendors: bool,
+    /// Only match names spelled exactly like a keyword or label, although PHP
+    /// identifiers are case-insensitive.
+    pub case_sensitive: bool,
+    /// Keep at most one soft and one hard match per keyword and file, so that counts
+    /// are of affected files rather than occurrences.
+    pub dedup_per_file: bool,
+    /// Keep where each match was found in the report, for the reports that list them.
+    /// Does not change which matches are found.
+    pub keep_locations: bool,
+}
+
+/// Keywords and labels are shared with `Arc`, so that each rayon worker can own an
+/// `Analyzer` without borrowing from the caller's stack.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Analyzer<'ctx> {
+pub struct Analyzer {
     hard: bool,
-    keywords: &'ctx [&'ctx str],
-    labels: &'ctx [&'ctx str],
+    case_sensitive: bool,
+    /// Record every name as if it were a keyword, and every label, for the match store.
+    record_all: bool,
+    keywords: Arc<[String]>,
+    labels: Arc<[String]>,
+    options: AnalysisOptions,
 }
 
-impl<'ctx> Analyzer<'ctx> {
-    #[tracing::instrument(name = "analyzing-file", skip(arena, sources_canonical, keywords))]
+impl Analyzer {
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(
+        name = "analyzing-file",
+        skip(arena, package_names, keywords, cache, match_store),
+        fields(package = tracing::field::Empty)
+    )]
     pub fn run<'arena>(
         arena: &'arena Bump,
         file: &Path,
-        sources_canonical: &Path,
-        keywords: &'ctx [&'ctx str],
-        labels: &'ctx [&'ctx str],
-    ) -> (Vec<KeywordMatch>, Vec<LabelMatch>) {
-        let Some((vendor, file)) = read_file(file, sources_canonical) else {
-            return (Vec::new(), Vec::new());
+        package_names: &PackageNames,
+        keywords: &Arc<[String]>,
+        labels: &Arc<[String]>,
+        cache: Option<&ParseCache>,
+        match_store: Option<&MatchStore>,
+        options: AnalysisOptions,
+    ) -> FileMatches {
+        let path = file;
+        let Some((vendor, file, is_non_utf8)) = read_file(path, package_names) else {
+            return FileMatches::default();
+        };
+
+        if let Some(cached) = cache.and_then(|cache| cache.get(&file)) {
+            return FileMatches {
+                is_non_utf8,
+                ..cached
+            };
+        }
+
+        let package = package_names.package_of(path);
+        let package = package.as_deref();
+        tracing::Span::current().record("package", package);
+
+        let mut matches = match match_store {
+            Some(match_store) => {
+                match match_store.get(&file, &vendor, package, keywords, labels, options) {
+                    // The store only keeps identifiers, so whether a stored file had
+                    // parse errors is not known.
+                    Some((keyword_matches, label_matches)) => FileMatches {
+                        keyword_matches,
+                        label_matches,
+                        has_parse_error: false,
+                        is_non_utf8,
+                    },
+                    None => {
+                        let (identifiers, has_parse_error) =
+                            Self::collect_identifiers(arena, vendor, package, &file);
+                        match_store.store(&file, &identifiers);
+
+                        let (keyword_matches, label_matches) =
+                            filter_matches(identifiers, keywords, labels, options);
+
+                        FileMatches {
+                            keyword_matches,
+                            label_matches,
+                            has_parse_error,
+                            is_non_utf8,
+                        }
+                    }
+                }
+            }
+            None => FileMatches {
+                is_non_utf8,
+                ..Self::run_on_file(arena, vendor, package, &file, keywords, labels, options)
+            },
+        };
+
+        // Cached after deduplicating, as the option is part of the cache key.
+        if options.dedup_per_file {
+            dedup_per_file(&mut matches.keyword_matches);
+        }
+
+        if let Some(cache) = cache {
+            cache.store(&file, &matches);
+        }
+
+        matches
+    }
+
+    pub fn run_on_file<'arena>(
+        arena: &'arena Bump,
+        vendor: Vendor,
+        package: Option<&str>,
+        file: &File,
+        keywords: &Arc<[String]>,
+        labels: &Arc<[String]>,
+        options: AnalysisOptions,
+    ) -> FileMatches {
+        let (program, parse_error) = parse_file(arena, file);
+        let resolved_names = NameResolver::new(arena).resolve(program);
+        let mut ctx = AnalysisContext::new(vendor, package, file, resolved_names);
+        let analyzer = Analyzer {
+            hard: true,
+            case_sensitive: options.case_sensitive,
+            record_all: false,
+            keywords: Arc::clone(keywords),
+            labels: Arc::clone(labels),
+            options,
         };
+        analyzer.walk_program(program, &mut ctx);
+
+        FileMatches {
+            keyword_matches: ctx.keyword_matches,
+            label_matches: ctx.label_matches,
+            has_parse_error: parse_error.is_some(),
+            is_non_utf8: false,
+        }
+    }
 
-        let (program, _) = parse_file(arena, &file);
+    /// Collects every name the analyzer would check and every label, whatever the
+    /// keywords, so they can be matched against any keyword later on.
+    fn collect_identifiers<'arena>(
+        arena: &'arena Bump,
+        vendor: Vendor,
+        package: Option<&str>,
+        file: &File,
+    ) -> (CachedMatches, bool) {
+        let (program, parse_error) = parse_file(arena, file);
         let resolved_names = NameResolver::new(arena).resolve(program);
-        let mut ctx = AnalysisContext::new(vendor, resolved_names);
+        let mut ctx = AnalysisContext::new(vendor, package, file, resolved_names);
         let analyzer = Analyzer {
             hard: true,
-            keywords,
-            labels,
+            case_sensitive: false,
+            record_all: true,
+            keywords: Arc::from([]),
+            labels: Arc::from([]),
+            options: AnalysisOptions {
+                analyze_string_keys: true,
+                include_variables: true,
+                ..AnalysisOptions::default()
+            },
         };
         analyzer.walk_program(program, &mut ctx);
 
-        (ctx.keyword_matches, ctx.label_matches)
+        (
+            (ctx.keyword_matches, ctx.label_matches),
+            parse_error.is_some(),
+        )
+    }
+
+    /// Compares a name found in the source with a keyword or label.
+    fn matches(&self, name: &str, expected: &str) -> bool {
+        if self.case_sensitive {
+            name == expected
+        } else {
+            name.eq_ignore_ascii_case(expected)
+        }
+    }
+
+    /// Records a match if `name` is one of the keywords, returning whether it was.
+    fn check_keyword(
+        &self,
+        name: &str,
+        kind: MatchKind,
+        is_hard: bool,
+        offset: u32,
+        ctx: &mut AnalysisContext<'_>,
+    ) -> bool {
+        if self.record_all {
+            ctx.keyword_matches.push(KeywordMatch {
+                keyword: name.to_string(),
+                vendor: ctx.vendor.clone(),
+                package: ctx.package.clone(),
+                file: ctx.file_name.clone(),
+                line: ctx.line_of(offset),
+                kind,
+                is_hard,
+                offset,
+            });
+
+            return true;
+        }
+
+        for keyword in self.keywords.iter() {
+            // A span per comparison is far too costly to keep in normal builds.
+            #[cfg(feature = "trace-keywords")]
+            let _span =
+                tracing::debug_span!("checking-keyword", keyword = keyword.as_str()).entered();
+
+            if self.matches(name, keyword) {
+                ctx.keyword_matches.push(KeywordMatch {
+                    keyword: keyword.clone(),
+                    vendor: ctx.vendor.clone(),
+                    package: ctx.package.clone(),
+                    file: ctx.file_name.clone(),
+                    line: ctx.line_of(offset),
+                    kind,
+                    is_hard,
+                    offset,
+                });
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Records the constant declared by `define('LET', 1)`, when its name is a string literal.
+    fn check_defined_constant(&self, define: &FunctionCall<'_>, ctx: &mut AnalysisContext<'_>) {
+        if !self.hard {
+            return;
+        }
+
+        let Some(Argument::Positional(argument)) = define.argument_list.arguments.first() else {
+            return;
+        };
+
+        let Expression::Literal(Literal::String(string)) = argument.value else {
+            return;
+        };
+
+        let Some(value) = string.value else {
+            return;
+        };
+
+        let last_segment = value.split('\\').next_back().unwrap_or_default();
+
+        self.check_keyword(
+            last_segment,
+            MatchKind::ConstantName,
+            true,
+            string.position().offset,
+            ctx,
+        );
+    }
+
+    /// Records a method call whose method is named directly (`$obj->let()`, `Foo::let()`),
+    /// as opposed to dynamically (`$obj->$name()`, `Foo::$name()`).
+    fn check_method_selector(
+        &self,
+        selector: &ClassLikeMemberSelector<'_>,
+        kind: MatchKind,
+        ctx: &mut AnalysisContext<'_>,
+    ) {
+        let ClassLikeMemberSelector::Identifier(identifier) = selector else {
+            return;
+        };
+
+        self.check_soft_name(identifier, kind, ctx);
+    }
+
+    /// Records a name that only its callers would have to rename, such as a method
+    /// name, and claims its identifier so that `walk_in_local_identifier` does not
+    /// count it a second time as a hard match.
+    fn check_soft_name(
+        &self,
+        name: &LocalIdentifier<'_>,
+        kind: MatchKind,
+        ctx: &mut AnalysisContext<'_>,
+    ) {
+        let offset = name.position().offset;
+        if self.check_keyword(name.value, kind, false, offset, ctx) {
+            ctx.claimed_identifiers.insert(offset);
+        }
+    }
+
+    /// Records a declared (or, for class constants, accessed) name with its specific
+    /// kind, and claims its identifier so that `walk_in_local_identifier` does not
+    /// count it a second time.
+    fn check_declared_name(
+        &self,
+        name: &LocalIdentifier<'_>,
+        kind: MatchKind,
+        ctx: &mut AnalysisContext<'_>,
+    ) {
+        if !self.hard {
+            return;
+        }
+
+        let offset = name.position().offset;
+        if self.check_keyword(name.value, kind, true, offset, ctx) {
+            ctx.claimed_identifiers.insert(offset);
+        }
+    }
+
+    /// Records a variable introduced by a closure or arrow function with its specific
+    /// kind, and claims it so that `walk_in_direct_variable` does not count it a second time.
+    fn check_variable_declaration(
+        &self,
+        variable: &DirectVariable<'_>,
+        kind: MatchKind,
+        ctx: &mut AnalysisContext<'_>,
+    ) {
+        if !self.options.include_variables {
+            return;
+        }
+
+        let offset = variable.position().offset;
+        let name = variable.name.trim_start_matches('$');
+        if self.check_keyword(name, kind, false, offset, ctx) {
+            ctx.claimed_identifiers.insert(offset);
+        }
+    }
+
+    fn check_label(&self, name: &str, ctx: &mut AnalysisContext<'_>) {
+        // Allocated once, and only if the label is reported.
+        let mut label: Option<Arc<str>> = None;
+        let mut shared_label = || label.get_or_insert_with(|| Arc::from(name)).clone();
+
+        if self.record_all {
+            ctx.label_matches.push(LabelMatch {
+                label: shared_label(),
+                vendor: ctx.vendor.clone(),
+            });
+        }
+
+        for label_v in self.labels.iter() {
+            if self.matches(name, label_v) {
+                ctx.label_matches.push(LabelMatch {
+                    label: shared_label(),
+                    vendor: ctx.vendor.clone(),
+                });
+            }
+        }
+    }
+
+    /// Checks every segment of a declared or imported namespace path, e.g. both
+    /// `Let` and `Bar` in `namespace Foo\Let\Bar;`. The last segment is reported as
+    /// `last_kind`, e.g. as the imported name of a `use` item.
+    ///
+    /// The name is claimed so that the identifier walkers, which only look at
+    /// the last segment, do not count it a second time.
+    fn check_namespace_segments(
+        &self,
+        name: &Identifier<'_>,
+        last_kind: MatchKind,
+        ctx: &mut AnalysisContext<'_>,
+    ) {
+        if !self.hard {
+            return;
+        }
+
+        let offset = name.position().offset;
+        let mut segment_offset = offset;
+        let mut segments = name.value().split('\\').peekable();
+        while let Some(segment) = segments.next() {
+            if !segment.is_empty() {
+                let kind = match segments.peek() {
+                    Some(_) => MatchKind::NamespaceSegment,
+                    None => last_kind,
+                };
+
+                self.check_keyword(segment, kind, true, segment_offset, ctx);
+            }
+
+            segment_offset += segment.len() as u32 + 1;
+        }
+
+        ctx.claimed_identifiers.insert(offset);
     }
 }
 
 pub struct AnalysisContext<'arena> {
     vendor: Vendor,
+    package: Option<String>,
+    file_name: String,
+    /// Byte offset at which each line of the file starts.
+    line_starts: Vec<u32>,
     resolved_names: ResolvedNames<'arena>,
     keyword_matches: Vec<KeywordMatch>,
     label_matches: Vec<LabelMatch>,
+    /// Offsets of identifiers already reported by a more specific walker.
+    claimed_identifiers: HashSet<u32>,
 }
 
 impl<'arena> AnalysisContext<'arena> {
-    pub fn new(vendor: Vendor, resolved_names: ResolvedNames<'arena>) -> Self {
+    pub fn new(
+        vendor: Vendor,
+        package: Option<&str>,
+        file: &File,
+        resolved_names: ResolvedNames<'arena>,
+    ) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(
+                file.contents
+                    .bytes()
+                    .enumerate()
+                    .filter(|(_, byte)| *byte == b'\n')
+                    .map(|(index, _)| index as u32 + 1),
+            )
+            .collect();
+
         Self {
             vendor,
+            package: package.map(str::to_string),
+            file_name: file.name.to_string(),
+            line_starts,
             resolved_names,
             keyword_matches: Vec::new(),
             label_matches: Vec::new(),
+            claimed_identifiers: HashSet::new(),
         }
     }
+
+    /// Returns the 1-based line number containing `offset`.
+    fn line_of(&self, offset: u32) -> u32 {
+        self.line_starts.partition_point(|&start| start <= offset) as u32
+    }
 }
 
-impl<'ctx, 'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analyzer<'ctx> {
+impl<'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analyzer {
     fn walk_in_label(&self, label: &'ast Label<'arena>, ctx: &mut AnalysisContext<'arena>) {
-        for label_v in self.labels {
-            if label.name.value.eq_ignore_ascii_case(label_v) {
-                ctx.label_matches.push(LabelMatch {
-                    label: label.name.value.to_string(),
-                    vendor: ctx.vendor,
-                });
-            }
-        }
+        self.check_label(label.name.value, ctx);
+    }
+
+    /// `goto end;` references the label declared by `end:`.
+    fn walk_in_goto(&self, goto: &'ast Goto<'arena>, ctx: &mut AnalysisContext<'arena>) {
+        self.check_label(goto.label.value, ctx);
     }
 
+    /// The label of `foo(let: $x)` is the `name` identifier of the named argument. Renaming
+    /// it only affects the caller, so it is a soft match.
     fn walk_in_named_argument(
         &self,
         named_argument: &'ast NamedArgument<'arena>,
         ctx: &mut AnalysisContext<'arena>,
     ) {
-        for label_v in self.labels {
-            if named_argument.name.value.eq_ignore_ascii_case(label_v) {
-                ctx.label_matches.push(LabelMatch {
-                    label: named_argument.name.value.to_string(),
-                    vendor: ctx.vendor,
-                });
-            }
+        self.check_soft_name(&named_argument.name, MatchKind::NamedArgument, ctx);
+
+        self.check_label(named_argument.name.value, ctx);
+    }
+
+    fn walk_in_key_value_array_element(
+        &self,
+        key_value_array_element: &'ast KeyValueArrayElement<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        if !self.options.analyze_string_keys {
+            return;
+        }
+
+        let Expression::Literal(Literal::String(string)) = key_value_array_element.key else {
+            return;
+        };
+
+        let Some(value) = string.value else {
+            return;
+        };
+
+        self.check_keyword(
+            value,
+            MatchKind::ArrayKey,
+            false,
+            string.position().offset,
+            context,
+        );
+    }
+
+    /// The label of `<<<LET ... LET;` (or `<<<'LET'` for nowdocs) is in no namespace, but
+    /// tokenizers that tell labels from keywords by matching them could break on it, so
+    /// it is a soft match. The parser keeps a single label, which is checked both where
+    /// the document opens and where it closes.
+    fn walk_in_document_string(
+        &self,
+        document_string: &'ast DocumentString<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        let kind = match document_string.kind {
+            DocumentKind::Heredoc => MatchKind::HeredocLabel,
+            DocumentKind::Nowdoc => MatchKind::NowdocLabel,
+        };
+        let label = document_string.label;
+
+        self.check_keyword(
+            label,
+            kind,
+            false,
+            document_string.open.start.offset,
+            context,
+        );
+        self.check_keyword(
+            label,
+            kind,
+            false,
+            document_string.close.end.offset - label.len() as u32,
+            context,
+        );
+    }
+
+    /// The directive names of `declare(strict_types=1)`. None of today's directives are
+    /// keyword candidates, but a new directive would be parsed like a keyword.
+    fn walk_in_declare(
+        &self,
+        declare: &'ast Declare<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        for item in declare.items.iter() {
+            self.check_declared_name(&item.name, MatchKind::DeclareDirective, context);
+        }
+    }
+
+    fn walk_in_class(&self, class: &'ast Class<'arena>, context: &mut AnalysisContext<'arena>) {
+        self.check_declared_name(&class.name, MatchKind::ClassName, context);
+    }
+
+    fn walk_in_interface(
+        &self,
+        interface: &'ast Interface<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_declared_name(&interface.name, MatchKind::InterfaceName, context);
+    }
+
+    fn walk_in_trait(&self, r#trait: &'ast Trait<'arena>, context: &mut AnalysisContext<'arena>) {
+        self.check_declared_name(&r#trait.name, MatchKind::TraitName, context);
+    }
+
+    fn walk_in_enum(&self, r#enum: &'ast Enum<'arena>, context: &mut AnalysisContext<'arena>) {
+        self.check_declared_name(&r#enum.name, MatchKind::EnumName, context);
+    }
+
+    /// Attribute names are resolved like class names, so `#[Let]` in `namespace App`
+    /// is checked as `App\Let`. Only the last segment is compared.
+    fn walk_in_attribute(
+        &self,
+        attribute: &'ast Attribute<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        if !self.hard {
+            return;
+        }
+
+        let resolved_name = context.resolved_names.get(&attribute.name);
+        let last_segment = resolved_name.split('\\').next_back().unwrap_or_default();
+
+        let offset = attribute.name.position().offset;
+        if self.check_keyword(
+            last_segment,
+            MatchKind::AttributeName,
+            true,
+            offset,
+            context,
+        ) {
+            context.claimed_identifiers.insert(offset);
+        }
+    }
+
+    fn walk_in_namespace(
+        &self,
+        namespace: &'ast Namespace<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        if let Some(name) = &namespace.name {
+            self.check_namespace_segments(name, MatchKind::NamespaceSegment, context);
         }
     }
 
+    /// `use Foo\Let;` makes a bare `Let` refer to the imported name, and so does
+    /// `use Foo as Let;` for the alias.
+    fn walk_in_use_item(
+        &self,
+        use_item: &'ast UseItem<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_namespace_segments(&use_item.name, MatchKind::ImportedName, context);
+
+        if let Some(alias) = &use_item.alias {
+            self.check_declared_name(&alias.identifier, MatchKind::ImportAlias, context);
+        }
+    }
+
+    /// The shared prefix of `use Foo\Let\{Bar, Baz};`.
+    fn walk_in_typed_use_item_list(
+        &self,
+        list: &'ast TypedUseItemList<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_namespace_segments(&list.namespace, MatchKind::NamespaceSegment, context);
+    }
+
+    fn walk_in_mixed_use_item_list(
+        &self,
+        list: &'ast MixedUseItemList<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_namespace_segments(&list.namespace, MatchKind::NamespaceSegment, context);
+    }
+
     fn walk_in_function_call(
         &self,
         function_call: &'ast FunctionCall<'arena>,
@@ -143,16 +1091,99 @@ impl<'ctx, 'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analy
         let resolved_name = ctx.resolved_names.get(identifier);
         let last_segment = resolved_name.split('\\').next_back().unwrap_or_default();
 
-        for &keyword in self.keywords {
-            if last_segment.eq_ignore_ascii_case(keyword) {
-                ctx.keyword_matches.push(KeywordMatch {
-                    keyword: keyword.to_string(),
-                    vendor: ctx.vendor,
-                    is_hard: false,
-                });
+        self.check_keyword(
+            last_segment,
+            MatchKind::FunctionCall,
+            false,
+            function_call.position().offset,
+            ctx,
+        );
 
-                break;
-            }
+        if last_segment.eq_ignore_ascii_case("define") {
+            self.check_defined_constant(function_call, ctx);
+        }
+    }
+
+    fn walk_in_direct_variable(
+        &self,
+        direct_variable: &'ast DirectVariable<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        if !self.options.include_variables {
+            return;
+        }
+
+        let offset = direct_variable.position().offset;
+        if context.claimed_identifiers.contains(&offset) {
+            return;
+        }
+
+        self.check_keyword(
+            direct_variable.name.trim_start_matches('$'),
+            MatchKind::Variable,
+            false,
+            offset,
+            context,
+        );
+    }
+
+    /// `use ($let)` captures a variable into a closure; reported with its own kind
+    /// instead of as a plain variable.
+    fn walk_in_closure_use_clause_variable(
+        &self,
+        closure_use_clause_variable: &'ast ClosureUseClauseVariable<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_variable_declaration(
+            &closure_use_clause_variable.variable,
+            MatchKind::ClosureUseVariable,
+            context,
+        );
+    }
+
+    /// The parameters of `fn($let) => $let`; the uses in the body stay plain variables.
+    fn walk_in_arrow_function(
+        &self,
+        arrow_function: &'ast ArrowFunction<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        for parameter in arrow_function.parameter_list.parameters.iter() {
+            self.check_variable_declaration(
+                &parameter.variable,
+                MatchKind::ArrowFunctionParameter,
+                context,
+            );
+        }
+    }
+
+    fn walk_in_constant_item(
+        &self,
+        constant_item: &'ast ConstantItem<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_declared_name(&constant_item.name, MatchKind::ConstantName, context);
+    }
+
+    fn walk_in_class_like_constant_item(
+        &self,
+        class_like_constant_item: &'ast ClassLikeConstantItem<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_declared_name(
+            &class_like_constant_item.name,
+            MatchKind::ClassConstantName,
+            context,
+        );
+    }
+
+    fn walk_in_class_constant_access(
+        &self,
+        class_constant_access: &'ast ClassConstantAccess<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        // `Foo::{$name}` names its constant dynamically.
+        if let ClassLikeConstantSelector::Identifier(identifier) = &class_constant_access.constant {
+            self.check_declared_name(identifier, MatchKind::ClassConstantAccess, context);
         }
     }
 
@@ -168,17 +1199,13 @@ impl<'ctx, 'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analy
         let resolved_name = context.resolved_names.get(identifier);
         let last_segment = resolved_name.split('\\').next_back().unwrap_or_default();
 
-        for &keyword in self.keywords {
-            if last_segment.eq_ignore_ascii_case(keyword) {
-                context.keyword_matches.push(KeywordMatch {
-                    keyword: keyword.to_string(),
-                    vendor: context.vendor,
-                    is_hard: false,
-                });
-
-                break;
-            }
-        }
+        self.check_keyword(
+            last_segment,
+            MatchKind::FunctionPartialApplication,
+            false,
+            function_partial_application.position().offset,
+            context,
+        );
     }
 
     fn walk_in_function(
@@ -186,19 +1213,64 @@ impl<'ctx, 'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analy
         function: &'ast Function<'arena>,
         context: &mut AnalysisContext<'arena>,
     ) {
-        let name = &function.name.value;
-
-        for &keyword in self.keywords {
-            if name.eq_ignore_ascii_case(keyword) {
-                context.keyword_matches.push(KeywordMatch {
-                    keyword: keyword.to_string(),
-                    vendor: context.vendor,
-                    is_hard: false,
-                });
+        self.check_keyword(
+            function.name.value,
+            MatchKind::FunctionName,
+            false,
+            function.name.position().offset,
+            context,
+        );
+    }
 
-                break;
-            }
-        }
+    fn walk_in_enum_case(
+        &self,
+        enum_case: &'ast EnumCase<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        let name = match &enum_case.item {
+            EnumCaseItem::Unit(item) => &item.name,
+            EnumCaseItem::Backed(item) => &item.name,
+        };
+
+        self.check_declared_name(name, MatchKind::EnumCaseName, context);
+    }
+
+    fn walk_in_method(&self, method: &'ast Method<'arena>, context: &mut AnalysisContext<'arena>) {
+        self.check_soft_name(&method.name, MatchKind::MethodName, context);
+    }
+
+    fn walk_in_method_call(
+        &self,
+        method_call: &'ast MethodCall<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_method_selector(&method_call.method, MatchKind::MethodCall, context);
+    }
+
+    fn walk_in_null_safe_method_call(
+        &self,
+        null_safe_method_call: &'ast NullSafeMethodCall<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        self.check_method_selector(
+            &null_safe_method_call.method,
+            MatchKind::MethodCall,
+            context,
+        );
+    }
+
+    fn walk_in_static_method_call(
+        &self,
+        static_method_call: &'ast StaticMethodCall<'arena>,
+        context: &mut AnalysisContext<'arena>,
+    ) {
+        // Unlike `Foo::LET`, which `walk_in_class_constant_access` handles, `Foo::let()`
+        // is a call, and breaks like any other method call once `let` is reserved.
+        self.check_method_selector(
+            &static_method_call.method,
+            MatchKind::StaticMethodCall,
+            context,
+        );
     }
 
     fn walk_in_local_identifier(
@@ -210,17 +1282,18 @@ impl<'ctx, 'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analy
             return;
         }
 
-        for &keyword in self.keywords {
-            if local_identifier.value.eq_ignore_ascii_case(keyword) {
-                context.keyword_matches.push(KeywordMatch {
-                    keyword: keyword.to_string(),
-                    vendor: context.vendor,
-                    is_hard: true,
-                });
-
-                break;
-            }
+        let offset = local_identifier.position().offset;
+        if context.claimed_identifiers.contains(&offset) {
+            return;
         }
+
+        self.check_keyword(
+            local_identifier.value,
+            MatchKind::Identifier,
+            true,
+            offset,
+            context,
+        );
     }
 
     fn walk_in_qualified_identifier(
@@ -233,7 +1306,9 @@ impl<'ctx, 'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analy
         }
 
         let position = qualified_identifier.position();
-        if !context.resolved_names.contains(&position) {
+        if context.claimed_identifiers.contains(&position.offset)
+            || !context.resolved_names.contains(&position)
+        {
             return;
         }
 
@@ -244,17 +1319,13 @@ impl<'ctx, 'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analy
             .next_back()
             .unwrap_or_default();
 
-        for &keyword in self.keywords {
-            if last_segment.eq_ignore_ascii_case(keyword) {
-                context.keyword_matches.push(KeywordMatch {
-                    keyword: keyword.to_string(),
-                    vendor: context.vendor,
-                    is_hard: true,
-                });
-
-                break;
-            }
-        }
+        self.check_keyword(
+            last_segment,
+            MatchKind::Identifier,
+            true,
+            position.offset,
+            context,
+        );
     }
 
     fn walk_in_fully_qualified_identifier(
@@ -266,22 +1337,75 @@ impl<'ctx, 'ast, 'arena> Walker<'ast, 'arena, AnalysisContext<'arena>> for Analy
             return;
         }
 
+        let offset = fully_qualified_identifier.position().offset;
+        if context.claimed_identifiers.contains(&offset) {
+            return;
+        }
+
         let last_segment = fully_qualified_identifier
             .value
             .split('\\')
             .next_back()
             .unwrap_or_default();
 
-        for &keyword in self.keywords {
-            if last_segment.eq_ignore_ascii_case(keyword) {
-                context.keyword_matches.push(KeywordMatch {
-                    keyword: keyword.to_string(),
-                    vendor: context.vendor,
-                    is_hard: true,
-                });
+        self.check_keyword(last_segment, MatchKind::Identifier, true, offset, context);
+    }
+}
 
-                break;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyword_matches(source: &str, keyword: &str) -> Vec<KeywordMatch> {
+        analyze_string(
+            source.to_string(),
+            &[keyword.to_string()],
+            &[],
+            AnalysisOptions::default(),
+        )
+        .keyword_matches
+    }
+
+    fn kinds(matches: &[KeywordMatch]) -> Vec<(MatchKind, bool)> {
+        matches.iter().map(|m| (m.kind, m.is_hard)).collect()
+    }
+
+    #[test]
+    fn class_like_names_are_hard_matches() {
+        for (source, keyword, kind) in [
+            ("<?php class Let {}", "let", MatchKind::ClassName),
+            (
+                "<?php interface Using {}",
+                "using",
+                MatchKind::InterfaceName,
+            ),
+            (
+                "<?php trait Scope { public function foo() {} }",
+                "scope",
+                MatchKind::TraitName,
+            ),
+            ("<?php enum Using {}", "using", MatchKind::EnumName),
+        ] {
+            let matches = keyword_matches(source, keyword);
+
+            assert_eq!(kinds(&matches), [(kind, true)], "{}", source);
         }
     }
+
+    #[test]
+    fn class_like_names_are_matched_case_insensitively() {
+        let matches = keyword_matches("<?php final class LET extends Base {}", "let");
+
+        assert_eq!(kinds(&matches), [(MatchKind::ClassName, true)]);
+        assert_eq!(matches[0].keyword, "let");
+    }
+
+    #[test]
+    fn call_in_arrow_function_body_is_a_soft_match() {
+        let matches = keyword_matches("<?php $x = fn() => let(1);", "let");
+
+        let soft: Vec<_> = matches.iter().filter(|m| !m.is_hard).collect();
+        assert_eq!(soft.len(), 1);
+        assert_eq!(soft[0].kind, MatchKind::FunctionCall);
+    }
 }
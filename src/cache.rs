@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use mago_database::file::File;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::analyzer::AnalysisOptions;
+use crate::results::CustomVendor;
+use crate::results::FileMatches;
+use crate::results::KeywordMatch;
+use crate::results::LabelMatch;
+
+pub type CachedMatches = (Vec<KeywordMatch>, Vec<LabelMatch>);
+
+/// Matches of previously analyzed files, keyed by their content, so that
+/// re-running an analysis does not parse unchanged files again.
+#[derive(Debug)]
+pub struct ParseCache {
+    directory: PathBuf,
+    fingerprint: String,
+}
+
+impl ParseCache {
+    pub fn new(
+        directory: PathBuf,
+        keywords: &[String],
+        labels: &[String],
+        options: AnalysisOptions,
+        custom_vendors: &[CustomVendor],
+    ) -> Result<Self> {
+        fs::create_dir_all(&directory).context("Failed to create parse cache directory")?;
+
+        // Matches depend on what we are looking for, so the searched keywords,
+        // labels and analysis options are part of every cache key. They are
+        // sorted so that the order they were given in does not matter. Custom
+        // vendors are too, as they change the vendor recorded with each match.
+        let mut keywords = keywords.to_vec();
+        keywords.sort();
+        let mut labels = labels.to_vec();
+        labels.sort();
+
+        Ok(Self {
+            directory,
+            fingerprint: format!(
+                "{}\0{}\0{:?}\0{:?}",
+                keywords.join("\n"),
+                labels.join("\n"),
+                // Only affects the report, not the matches of a file.
+                AnalysisOptions {
+                    keep_locations: false,
+                    ..options
+                },
+                custom_vendors
+            ),
+        })
+    }
+
+    pub fn clear(directory: &Path) -> Result<()> {
+        if directory.exists() {
+            fs::remove_dir_all(directory).context("Failed to clear parse cache directory")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, file: &File) -> Option<FileMatches> {
+        let bytes = fs::read(self.entry_path(file)).ok()?;
+
+        bincode::deserialize(&bytes).ok()
+    }
+
+    pub fn store(&self, file: &File, matches: &FileMatches) {
+        let entry_path = self.entry_path(file);
+
+        let bytes = match bincode::serialize(matches) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::debug!("Failed to serialize cache entry for {}: {}", file.name, e);
+                return;
+            }
+        };
+
+        if let Err(e) = fs::write(&entry_path, bytes) {
+            tracing::debug!("Failed to write cache entry {:?}: {}", entry_path, e);
+        }
+    }
+
+    /// Matches carry the file name and line numbers, so the name is part of the
+    /// key along with the content.
+    fn entry_path(&self, file: &File) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(file.contents.as_bytes());
+        hasher.update([0]);
+        hasher.update(file.name.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.fingerprint.as_bytes());
+
+        self.directory.join(format!("{:x}.bin", hasher.finalize()))
+    }
+}
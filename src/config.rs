@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Command;
+use serde::Deserialize;
+
+/// Read from the current directory when `--config` is not given.
+pub const DEFAULT_CONFIG_FILE: &str = "php-syntax-analyzer.toml";
+
+/// Analysis settings committed to a project, keyed by the snake_case name of
+/// the command line option they set (e.g. `max_packages = 100`).
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+pub struct Config {
+    settings: BTreeMap<String, toml::Value>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {:?}", path))?;
+
+        toml::from_str(&contents).with_context(|| format!("Invalid config file {:?}", path))
+    }
+
+    /// Turns the settings into command line arguments for `command`, to be placed
+    /// before the actual arguments so that those take precedence.
+    pub fn to_args(&self, command: &Command) -> Result<Vec<String>> {
+        let mut args = Vec::new();
+        for (key, value) in &self.settings {
+            let long = command
+                .get_arguments()
+                .find(|arg| arg.get_id() == key.as_str() && key != "config")
+                .and_then(|arg| arg.get_long())
+                .with_context(|| format!("Unknown setting {:?} in config file", key))?;
+
+            let values = match value {
+                toml::Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            };
+
+            for value in values {
+                match value {
+                    toml::Value::Boolean(true) => args.push(format!("--{}", long)),
+                    toml::Value::Boolean(false) => {}
+                    toml::Value::String(value) => args.push(format!("--{}={}", long, value)),
+                    toml::Value::Integer(value) => args.push(format!("--{}={}", long, value)),
+                    toml::Value::Float(value) => args.push(format!("--{}={}", long, value)),
+                    _ => anyhow::bail!("Unsupported value for setting {:?} in config file", key),
+                }
+            }
+        }
+
+        Ok(args)
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+const CORPUS_SEED: u64 = 0x5eed;
+const FILLER_WORDS: &[&str] = &[
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliet",
+];
+
+#[tracing::instrument(name = "generating-corpus", skip(keywords))]
+pub fn generate_corpus(
+    directory: &Path,
+    size: usize,
+    keywords: &[String],
+    density: f64,
+) -> Result<usize> {
+    // Mirror the `sources/<vendor>/<package>` layout so the corpus can be
+    // analyzed with `--directory <DIR> --skip-download`.
+    let corpus_dir = directory.join("sources").join("synthetic").join("corpus");
+    fs::create_dir_all(&corpus_dir).context("Failed to create corpus directory")?;
+
+    let mut rng = StdRng::seed_from_u64(CORPUS_SEED);
+    for index in 0..size {
+        let source = generate_file(&mut rng, keywords, density);
+        let path = corpus_dir.join(format!("file_{}.php", index));
+
+        fs::write(&path, source).with_context(|| format!("Failed to write {:?}", path))?;
+    }
+
+    tracing::info!("Generated {} synthetic PHP files in {:?}", size, corpus_dir);
+
+    Ok(size)
+}
+
+fn identifier(rng: &mut StdRng, keywords: &[String], density: f64) -> String {
+    if !keywords.is_empty() && rng.random_bool(density) {
+        return keywords[rng.random_range(0..keywords.len())].clone();
+    }
+
+    format!(
+        "{}{}",
+        FILLER_WORDS[rng.random_range(0..FILLER_WORDS.len())],
+        rng.random_range(0..1000)
+    )
+}
+
+fn generate_file(rng: &mut StdRng, keywords: &[String], density: f64) -> String {
+    let mut source = String::from("<?php\n\n");
+
+    let _ = writeln!(
+        source,
+        "namespace Synthetic\\{};\n",
+        identifier(rng, keywords, density)
+    );
+
+    for _ in 0..rng.random_range(1..=8) {
+        match rng.random_range(0..4) {
+            0 => {
+                let _ = writeln!(source, "class {} {{", identifier(rng, keywords, density));
+                for _ in 0..rng.random_range(1..=4) {
+                    let _ = writeln!(
+                        source,
+                        "    public function {}() {{ return ${}; }}",
+                        identifier(rng, keywords, density),
+                        identifier(rng, keywords, density)
+                    );
+                }
+                let _ = writeln!(source, "}}\n");
+            }
+            1 => {
+                let _ = writeln!(
+                    source,
+                    "function {}(${}) {{ return {}(${}); }}\n",
+                    identifier(rng, keywords, density),
+                    identifier(rng, keywords, density),
+                    identifier(rng, keywords, density),
+                    identifier(rng, keywords, density)
+                );
+            }
+            2 => {
+                let _ = writeln!(
+                    source,
+                    "${}->{}();\n",
+                    identifier(rng, keywords, density),
+                    identifier(rng, keywords, density)
+                );
+            }
+            _ => {
+                let _ = writeln!(
+                    source,
+                    "${} = {}(1);\n",
+                    identifier(rng, keywords, density),
+                    identifier(rng, keywords, density)
+                );
+            }
+        }
+    }
+
+    source
+}
@@ -1,23 +1,219 @@
 use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::future::Future;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
-use anyhow::{Context, Result};
-use futures::stream::{self, StreamExt};
+use anyhow::Context;
+use anyhow::Result;
+use clap::ValueEnum;
+use futures::stream;
+use futures::stream::StreamExt;
+use futures::stream::TryStreamExt;
 use reqwest::Client;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
 use serde::Deserialize;
+use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
+use sha2::Sha256;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::manifest::DownloadManifest;
+use crate::manifest::ManifestEntry;
+use crate::php_version;
+use crate::progress;
 
 const PACKAGIST_PER_PAGE: usize = 15;
-const MAX_CONCURRENT_DOWNLOADS: usize = 500;
+/// Default number of packages downloaded, and of metadata requests sent, at the same time;
+/// low enough for shared CI machines.
+pub const MAX_CONCURRENT_DOWNLOADS: usize = 5;
+/// Sent unless `--user-agent` is given, e.g. `keyword-impact-analyzer/0.1.0`.
+pub const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// How often, and how patiently, transient HTTP failures are retried.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: usize,
+    pub initial_delay: Duration,
+}
+
+/// Where the package list and package metadata are fetched from, so that private
+/// mirrors (Private Packagist, Satis) can be used instead of packagist.org.
+#[derive(Debug, Clone)]
+pub struct PackagistConfig {
+    pub search_base: String,
+    pub metadata_base: String,
+    /// Only list packages of this Composer type (e.g. `symfony-bundle`).
+    pub search_type: Option<String>,
+    /// List the results of this full-text search instead of the popular packages.
+    pub search_query: Option<String>,
+}
+
+impl PackagistConfig {
+    pub fn new(
+        url: Option<&str>,
+        metadata_url: Option<&str>,
+        search_type: Option<&str>,
+        search_query: Option<&str>,
+    ) -> Self {
+        let search_base = url.unwrap_or("https://packagist.org");
+        let metadata_base = metadata_url.or(url).unwrap_or("https://repo.packagist.org");
+
+        Self {
+            search_base: search_base.trim_end_matches('/').to_string(),
+            metadata_base: metadata_base.trim_end_matches('/').to_string(),
+            search_type: search_type.map(str::to_string),
+            search_query: search_query.map(str::to_string),
+        }
+    }
+}
+
+/// Which of a package's versions is downloaded.
+///
+/// Branches (`dev-master`, `2.x-dev`) are only downloaded when a package has
+/// nothing else, since their archives are snapshots that may be missing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum VersionStrategy {
+    /// The most recent version, including pre-releases
+    Latest,
+    /// The most recent version that is not a dev, alpha, beta or RC release
+    #[default]
+    LatestStable,
+    /// The first version ever published
+    Oldest,
+}
+
+impl VersionStrategy {
+    /// Returns the index of the version to download in `versions`, which is ordered
+    /// from latest to oldest, as Packagist lists them, and must not be empty.
+    fn select(self, versions: &[VersionInfo], package_name: &str) -> usize {
+        let is_release = |version: &VersionInfo| !is_dev_branch(&version.version);
+        let index = match self {
+            VersionStrategy::Latest => versions.iter().position(is_release),
+            VersionStrategy::Oldest => versions.iter().rposition(is_release),
+            VersionStrategy::LatestStable => versions
+                .iter()
+                .position(|version| is_stable(&version.version))
+                .or_else(|| {
+                    let index = versions.iter().position(is_release)?;
+                    tracing::warn!(
+                        "{} has no stable version, using the latest one ({})",
+                        package_name,
+                        versions[index].version
+                    );
+                    Some(index)
+                }),
+        };
+
+        // Packagist's p2 metadata lists branches separately in `~dev.json`, so this is
+        // only reached for repositories that list them along with releases.
+        index.unwrap_or_else(|| {
+            let index = versions
+                .iter()
+                .position(|version| version.version == "dev-master")
+                .unwrap_or(0);
+            tracing::debug!(
+                "{} only has dev versions, falling back to {}",
+                package_name,
+                versions[index].version
+            );
+            index
+        })
+    }
+}
+
+/// Whether `version` is a branch snapshot, e.g. `dev-master` or `2.x-dev`,
+/// rather than a tagged release.
+fn is_dev_branch(version: &str) -> bool {
+    let version = version.to_lowercase();
+
+    version.starts_with("dev-") || version.ends_with("-dev")
+}
+
+fn is_stable(version: &str) -> bool {
+    let version = version.to_lowercase();
+
+    !is_dev_branch(&version)
+        && !["-dev", "-alpha", "-beta", "-rc"]
+            .iter()
+            .any(|suffix| version.contains(suffix))
+}
+
+/// Network settings shared by every request made while downloading packages.
+#[derive(Debug, Clone, Copy)]
+pub struct HttpOptions<'a> {
+    pub headers: &'a [String],
+    pub user_agent: &'a str,
+    pub packagist: &'a PackagistConfig,
+    pub retry: RetryPolicy,
+    pub requests_per_second: u32,
+    /// Number of archives downloaded at the same time.
+    pub concurrency: usize,
+    /// Number of package metadata documents fetched at the same time, before any
+    /// archive is downloaded.
+    pub metadata_concurrency: usize,
+    /// Number of Packagist listing pages fetched at the same time.
+    pub parallel_pages: usize,
+    /// Compare downloaded archives against the `shasum` advertised by Packagist.
+    pub verify_checksums: bool,
+    pub version_strategy: VersionStrategy,
+}
+
+/// Spaces out requests so that, across all concurrent downloads, no more than
+/// `requests_per_second` are sent.
+#[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until the next request is allowed to be sent.
+    async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+
+        tokio::time::sleep_until(slot.into()).await;
+    }
+}
 
 #[derive(Debug, Deserialize)]
 struct PackageListResponse {
+    /// Search results come as `results` instead of `packages`.
+    #[serde(alias = "results")]
     packages: Vec<PackageItem>,
 }
 
 #[derive(Debug, Deserialize)]
 struct PackageItem {
     name: String,
+    #[serde(default)]
+    downloads: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -27,59 +223,271 @@ struct PackageDetailsResponse {
 
 #[derive(Debug, Deserialize)]
 struct VersionInfo {
+    version: String,
     dist: Option<DistInfo>,
+    /// Packagist's minified metadata only lists `require` when it changed from the
+    /// previous version, and uses the `"__unset"` string when it was removed.
+    require: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 struct DistInfo {
     url: String,
+    /// `zip` or `tar`; the latter is served gzipped.
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    /// SHA-1 or SHA-256 of the archive; often empty for GitHub zipballs.
+    #[serde(default)]
+    shasum: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageStatsResponse {
+    downloads: DownloadStats,
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadStats {
+    total: u64,
+}
+
+/// Runs `operation`, retrying it with exponential backoff while it fails with a
+/// transient error (see [`is_transient`]).
+async fn retry_with_backoff<T, F, Fut>(
+    policy: RetryPolicy,
+    description: &str,
+    mut operation: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = policy.initial_delay;
+    let mut retries = 0;
+
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if retries < policy.max_retries && is_transient(&e) => {
+                retries += 1;
+                tracing::debug!(
+                    "{} failed ({:#}), retrying in {}ms ({}/{})",
+                    description,
+                    e,
+                    delay.as_millis(),
+                    retries,
+                    policy.max_retries
+                );
+
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            Err(e) => return Err(e),
+        }
+    }
 }
 
-#[tracing::instrument(name = "fetching-package-list-from-packagist", skip(client))]
-async fn get_top_packages(client: &Client, min: usize, max: usize) -> Result<Vec<String>> {
-    let mut packages = Vec::new();
-    let mut page = (min / PACKAGIST_PER_PAGE) + 1;
-    let mut collected = 0;
+/// Rate limiting, server errors and network failures are worth retrying;
+/// any other HTTP status (e.g. 403 or 404) will not go away by itself.
+fn is_transient(error: &anyhow::Error) -> bool {
+    let Some(error) = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<reqwest::Error>())
+    else {
+        return false;
+    };
 
+    match error.status() {
+        Some(status) => matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        ),
+        None => error.is_timeout() || error.is_connect() || error.is_request() || error.is_body(),
+    }
+}
+
+/// Fetches the given pages of Packagist's popular packages (or search results, see
+/// [`PackagistConfig::search_query`]), `parallel_pages` at a time, and returns their
+/// packages in ranking order.
+async fn get_popular_pages(
+    client: &Client,
+    limiter: &RateLimiter,
+    pages: RangeInclusive<usize>,
+    http: HttpOptions<'_>,
+) -> Result<Vec<PackageItem>> {
+    let mut package_lists: Vec<(usize, Vec<PackageItem>)> = stream::iter(pages)
+        .map(|page| async move {
+            let package_list = retry_with_backoff(http.retry, "Fetching the package list", || {
+                get_popular_page(client, limiter, http.packagist, page)
+            })
+            .await?;
+
+            Ok::<_, anyhow::Error>((page, package_list))
+        })
+        .buffer_unordered(http.parallel_pages)
+        .try_collect()
+        .await?;
+
+    // Pages complete in any order, but the ranking depends on it.
+    package_lists.sort_by_key(|(page, _)| *page);
+
+    Ok(package_lists
+        .into_iter()
+        .flat_map(|(_, package_list)| package_list)
+        .collect())
+}
+
+#[tracing::instrument(
+    name = "fetching-package-list-from-packagist",
+    skip(client, limiter, http)
+)]
+async fn get_top_packages(
+    client: &Client,
+    limiter: &RateLimiter,
+    min: usize,
+    max: usize,
+    http: HttpOptions<'_>,
+) -> Result<Vec<String>> {
     tracing::info!(
         "Fetching top packages from Packagist (min: {}, max: {})",
         min,
         max
     );
 
-    loop {
-        let url = format!("https://packagist.org/explore/popular.json?page={}", page);
+    let first_page = (min / PACKAGIST_PER_PAGE) + 1;
+    let last_page = max.div_ceil(PACKAGIST_PER_PAGE);
+    let package_list = get_popular_pages(client, limiter, first_page..=last_page, http).await?;
 
-        tracing::debug!("Fetching page {}: {}", page, url);
+    let packages: Vec<String> = package_list
+        .into_iter()
+        .skip(min - (first_page - 1) * PACKAGIST_PER_PAGE)
+        .take(max - min)
+        .map(|package| package.name)
+        .collect();
 
-        let response = client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to fetch package list")?;
+    tracing::info!("Collected {} packages", packages.len());
 
-        let package_list: PackageListResponse = response
-            .json()
-            .await
-            .context("Failed to parse package list JSON")?;
+    Ok(packages)
+}
 
-        for package in package_list.packages {
-            if collected >= min && collected < max {
-                packages.push(package.name);
-            }
-            collected += 1;
-            if collected >= max {
-                tracing::info!("Collected {} packages", packages.len());
-                return Ok(packages);
+/// Like [`get_top_packages`], but ranks packages by their total download count
+/// instead of Packagist's popularity, which favours recent downloads.
+///
+/// Packagist has no listing sorted by total downloads, so this re-ranks the first
+/// `max` popular packages, which is where the most downloaded ones live.
+#[tracing::instrument(
+    name = "fetching-package-list-by-downloads",
+    skip(client, limiter, http)
+)]
+async fn get_top_packages_by_downloads(
+    client: &Client,
+    limiter: &RateLimiter,
+    min: usize,
+    max: usize,
+    http: HttpOptions<'_>,
+) -> Result<Vec<String>> {
+    tracing::info!(
+        "Fetching top packages by downloads from Packagist (min: {}, max: {})",
+        min,
+        max
+    );
+
+    let last_page = max.div_ceil(PACKAGIST_PER_PAGE);
+    let mut candidates = get_popular_pages(client, limiter, 1..=last_page, http).await?;
+
+    candidates.sort_by(|a, b| b.downloads.cmp(&a.downloads));
+
+    let packages: Vec<String> = candidates
+        .into_iter()
+        .take(max)
+        .skip(min)
+        .map(|package| package.name)
+        .collect();
+
+    tracing::info!("Collected {} packages", packages.len());
+
+    Ok(packages)
+}
+
+async fn get_popular_page(
+    client: &Client,
+    limiter: &RateLimiter,
+    packagist: &PackagistConfig,
+    page: usize,
+) -> Result<Vec<PackageItem>> {
+    let mut url = match &packagist.search_query {
+        Some(query) => format!(
+            "{}/search.json?q={}&page={}",
+            packagist.search_base,
+            urlencoding::encode(query),
+            page
+        ),
+        None => format!(
+            "{}/explore/popular.json?page={}",
+            packagist.search_base, page
+        ),
+    };
+    if let Some(search_type) = &packagist.search_type {
+        url.push_str(&format!("&type={}", urlencoding::encode(search_type)));
+    }
+
+    tracing::debug!("Fetching page {}: {}", page, url);
+
+    limiter.acquire().await;
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch package list")?
+        .error_for_status()
+        .with_context(|| format!("Packagist returned an error for page {}", page))?;
+
+    let package_list: PackageListResponse = response
+        .json()
+        .await
+        .context("Failed to parse package list JSON")?;
+
+    Ok(package_list.packages)
+}
+
+/// Resolves the effective `require.php` constraint of the version at `index`,
+/// expanding Packagist's minified metadata format along the way.
+fn php_constraint(versions: &[VersionInfo], index: usize) -> Option<&str> {
+    let mut constraint = None;
+    for version in &versions[..=index] {
+        match &version.require {
+            Some(serde_json::Value::Object(require)) => {
+                constraint = require.get("php").and_then(|php| php.as_str());
             }
+            Some(_) => constraint = None,
+            None => {}
         }
-
-        page += 1;
     }
+
+    constraint
+}
+
+/// The version of a package that would be downloaded.
+#[derive(Debug)]
+struct ResolvedPackage {
+    name: String,
+    version: String,
+    dist: DistInfo,
 }
 
-#[tracing::instrument(name = "downloading-package", skip(client, target_dir))]
-async fn download_package(client: &Client, package_name: &str, target_dir: &Path) -> Result<()> {
+/// Resolves the version of a package picked by the version strategy, returning
+/// `None` if it is not compatible with the requested PHP version.
+async fn resolve_package(
+    client: &Client,
+    limiter: &RateLimiter,
+    http: HttpOptions<'_>,
+    package_name: &str,
+    php_version: Option<&str>,
+) -> Result<Option<ResolvedPackage>> {
     let package_name_lower = package_name.to_lowercase();
 
     tracing::debug!("Processing package: {}", package_name);
@@ -90,111 +498,753 @@ async fn download_package(client: &Client, package_name: &str, target_dir: &Path
     }
     let (vendor, package) = (parts[0], parts[1]);
 
-    let metadata_url = format!("https://repo.packagist.org/p2/{}/{}.json", vendor, package);
+    let metadata_url = format!(
+        "{}/p2/{}/{}.json",
+        http.packagist.metadata_base, vendor, package
+    );
 
+    limiter.acquire().await;
     let response = client
         .get(&metadata_url)
         .send()
         .await
-        .context("Failed to fetch package metadata")?;
+        .context("Failed to fetch package metadata")?
+        .error_for_status()
+        .with_context(|| format!("Packagist returned an error for {}", package_name))?;
 
-    let details: PackageDetailsResponse = response
+    let mut details: PackageDetailsResponse = response
         .json()
         .await
         .context("Failed to parse package metadata")?;
 
-    let versions = details
+    let mut versions = details
         .packages
-        .get(&package_name_lower)
+        .remove(&package_name_lower)
         .context("Package not found in metadata")?;
 
     if versions.is_empty() {
         anyhow::bail!("No versions available for package");
     }
 
-    let version_info = versions.last().context("No suitable version found")?;
+    let index = http.version_strategy.select(&versions, package_name);
+    let version_info = &versions[index];
+
+    tracing::debug!(
+        "Selected version {} for {}",
+        version_info.version,
+        package_name
+    );
+
+    if let Some(php_version) = php_version {
+        match php_constraint(&versions, index) {
+            None => {
+                tracing::debug!("{} has no PHP version constraint", package_name);
+            }
+            Some(constraint) => match php_version::satisfies(constraint, php_version) {
+                Ok(true) => {
+                    tracing::debug!(
+                        "{} ({}) is compatible with PHP {}",
+                        package_name,
+                        constraint,
+                        php_version
+                    );
+                }
+                Ok(false) => {
+                    tracing::debug!(
+                        "Skipping {} ({}): not compatible with PHP {}",
+                        package_name,
+                        constraint,
+                        php_version
+                    );
+                    return Ok(None);
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping {}: {}", package_name, e);
+                    return Ok(None);
+                }
+            },
+        }
+    }
+
+    let version_info = versions.swap_remove(index);
+    let dist = version_info.dist.context("No dist information available")?;
+
+    Ok(Some(ResolvedPackage {
+        name: package_name_lower,
+        version: version_info.version,
+        dist,
+    }))
+}
+
+/// Fetches the total downloads of a package from its Packagist statistics.
+async fn fetch_total_downloads(
+    client: &Client,
+    limiter: &RateLimiter,
+    http: HttpOptions<'_>,
+    package_name: &str,
+) -> Result<u64> {
+    let stats_url = format!(
+        "{}/packages/{}/stats.json",
+        http.packagist.search_base, package_name
+    );
+
+    limiter.acquire().await;
+    let response = client
+        .get(&stats_url)
+        .send()
+        .await
+        .context("Failed to fetch package statistics")?
+        .error_for_status()
+        .with_context(|| {
+            format!(
+                "Packagist returned an error for {} statistics",
+                package_name
+            )
+        })?;
+
+    let stats: PackageStatsResponse = response
+        .json()
+        .await
+        .context("Failed to parse package statistics")?;
+
+    Ok(stats.downloads.total)
+}
+
+/// Returns the total downloads of a package for `--min-downloads`, or `None` when
+/// no minimum is set. `cached` is used instead of fetching them again when known.
+async fn total_downloads(
+    client: &Client,
+    limiter: &RateLimiter,
+    http: HttpOptions<'_>,
+    package_name: &str,
+    min_downloads: u64,
+    cached: Option<u64>,
+) -> Result<Option<u64>> {
+    if min_downloads == 0 {
+        return Ok(None);
+    }
+
+    if let Some(downloads) = cached {
+        return Ok(Some(downloads));
+    }
 
-    tracing::debug!("Selected version for {}", package_name);
+    let description = format!("Fetching statistics of {}", package_name);
+    retry_with_backoff(http.retry, &description, || {
+        fetch_total_downloads(client, limiter, http, package_name)
+    })
+    .await
+    .map(Some)
+}
 
-    let dist = version_info
-        .dist
-        .as_ref()
-        .context("No dist information available")?;
+/// Downloads the archive of a package resolved by [`resolve_package`].
+#[tracing::instrument(
+    name = "downloading-package",
+    skip_all,
+    fields(package = %resolved.name)
+)]
+async fn download_dist(
+    client: &Client,
+    limiter: &RateLimiter,
+    http: HttpOptions<'_>,
+    resolved: &ResolvedPackage,
+    target_dir: &Path,
+) -> Result<ManifestEntry> {
+    let package_name = &resolved.name;
+    let dist = &resolved.dist;
 
-    let zipball_dir = target_dir.join("zipballs").join(&package_name_lower);
+    let zipball_dir = target_dir.join("zipballs").join(package_name);
     fs::create_dir_all(&zipball_dir).context("Failed to create zipball directory")?;
 
-    let zipball_path = zipball_dir.join(format!("{}.zip", package_name_lower.replace('/', "-")));
+    let extension = match dist.kind.as_deref() {
+        Some("tar") => "tar.gz",
+        _ => "zip",
+    };
+    let archive_name = format!("{}.{}", package_name.replace('/', "-"), extension);
+    let zipball_path = zipball_dir.join(&archive_name);
+
+    let entry = |sha1: String| ManifestEntry {
+        package: package_name.clone(),
+        version: resolved.version.clone(),
+        sha1,
+        downloaded_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default(),
+    };
 
     if zipball_path.exists() {
         tracing::debug!("Package {} already downloaded, skipping", package_name);
-        return Ok(());
+        let mut zipball = fs::File::open(&zipball_path).context("Failed to open zipball")?;
+        let mut hasher = Sha1::new();
+        io::copy(&mut zipball, &mut hasher).context("Failed to read zipball")?;
+        return Ok(entry(format!("{:x}", hasher.finalize())));
     }
 
     tracing::debug!("Downloading {} from {}", package_name, dist.url);
 
+    limiter.acquire().await;
     let response = client
         .get(&dist.url)
         .send()
         .await
-        .context("Failed to download package")?;
+        .context("Failed to download package")?
+        .error_for_status()
+        .with_context(|| format!("Failed to download dist for {}", package_name))?;
 
-    let bytes = response
-        .bytes()
+    // Stream the body to a partial file so large archives are never held in memory,
+    // and an interrupted download is not mistaken for a complete one.
+    let total_size = response.content_length().filter(|size| *size > 0);
+    let partial_path = zipball_dir.join(format!("{}.part", archive_name));
+    let mut file = tokio::fs::File::create(&partial_path)
         .await
-        .context("Failed to read package bytes")?;
+        .context("Failed to create zipball")?;
 
-    fs::write(&zipball_path, &bytes).context("Failed to write zipball")?;
+    let mut body = response.bytes_stream();
+    let mut hasher = Sha1::new();
+    let mut sha256 = Sha256::new();
+    let mut downloaded: u64 = 0;
+    let mut last_reported = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.context("Failed to read package bytes")?;
+        file.write_all(&chunk)
+            .await
+            .context("Failed to write zipball")?;
+        hasher.update(&chunk);
+        sha256.update(&chunk);
 
-    tracing::debug!("Downloaded {} bytes to {:?}", bytes.len(), zipball_path);
+        downloaded += chunk.len() as u64;
+        if let Some(total_size) = total_size {
+            let percentage = downloaded * 100 / total_size;
+            if percentage >= last_reported + 10 {
+                last_reported = percentage;
+                tracing::trace!("Downloading {}: {}%", package_name, percentage);
+            }
+        }
+    }
 
-    Ok(())
+    file.flush().await.context("Failed to write zipball")?;
+    drop(file);
+
+    let sha1 = format!("{:x}", hasher.finalize());
+    if let Some(expected) = dist.shasum.as_deref().filter(|_| http.verify_checksums) {
+        let (algorithm, actual) = match expected.len() {
+            64 => ("SHA-256", format!("{:x}", sha256.finalize())),
+            _ => ("SHA-1", sha1.clone()),
+        };
+
+        if !expected.is_empty() && !actual.eq_ignore_ascii_case(expected) {
+            let _ = tokio::fs::remove_file(&partial_path).await;
+            anyhow::bail!(
+                "{} checksum mismatch for {}: expected {}, got {}",
+                algorithm,
+                package_name,
+                expected,
+                actual
+            );
+        }
+    }
+
+    tokio::fs::rename(&partial_path, &zipball_path)
+        .await
+        .context("Failed to move zipball into place")?;
+
+    tracing::debug!("Downloaded {} bytes to {:?}", downloaded, zipball_path);
+
+    Ok(entry(sha1))
+}
+
+fn build_header_map(headers: &[String]) -> Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+
+    for header in headers {
+        let (name, value) = header
+            .split_once('=')
+            .with_context(|| format!("Invalid HTTP header {:?}, expected NAME=VALUE", header))?;
+
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .with_context(|| format!("Invalid HTTP header name {:?}", name))?;
+        let mut value = HeaderValue::from_str(value.trim())
+            .with_context(|| format!("Invalid value for HTTP header {}", name))?;
+
+        let lower_name = name.as_str();
+        let is_sensitive = ["auth", "token", "secret"]
+            .iter()
+            .any(|needle| lower_name.contains(needle));
+
+        if is_sensitive {
+            value.set_sensitive(true);
+            tracing::debug!("Adding HTTP header {}: ********", name);
+        } else {
+            tracing::debug!("Adding HTTP header {}: {:?}", name, value);
+        }
+
+        map.append(name, value);
+    }
+
+    Ok(map)
+}
+
+/// Builds the client for every request. Compressed responses are decoded
+/// transparently, and reqwest sends the matching `Accept-Encoding` unless an
+/// `--http-header` sets its own.
+fn build_client(http: HttpOptions<'_>) -> Result<Client> {
+    Client::builder()
+        .user_agent(http.user_agent)
+        .default_headers(build_header_map(http.headers)?)
+        .gzip(true)
+        .brotli(true)
+        .deflate(true)
+        .build()
+        .context("Failed to create HTTP client")
+}
+
+/// A package that `--dry-run` would download.
+#[derive(Debug, Serialize)]
+pub struct PlannedDownload {
+    pub package: String,
+    pub version: String,
+    pub url: String,
+}
+
+/// A package that could not be resolved or downloaded, e.g. because it was not
+/// found, its checksum did not match or the network timed out.
+#[derive(Debug, Serialize)]
+pub struct DownloadFailure {
+    pub package: String,
+    pub reason: String,
+}
+
+/// Which packages to download.
+#[derive(Debug, Clone)]
+pub enum PackageSelection {
+    /// Packagist's popular packages from index `min` (inclusive) to `max` (exclusive).
+    /// If `by_downloads` is set, the first `max` popular packages are re-ranked by total
+    /// downloads before the range is taken, so packages outside them are never selected.
+    Top {
+        min: usize,
+        max: usize,
+        by_downloads: bool,
+    },
+    /// Exactly these `vendor/package` names, e.g. for a reproducible analysis.
+    Names(Vec<String>),
+}
+
+async fn list_packages(
+    client: &Client,
+    limiter: &RateLimiter,
+    selection: &PackageSelection,
+    http: HttpOptions<'_>,
+) -> Result<Vec<String>> {
+    match *selection {
+        PackageSelection::Top {
+            min,
+            max,
+            by_downloads: true,
+        } => get_top_packages_by_downloads(client, limiter, min, max, http).await,
+        PackageSelection::Top { min, max, .. } => {
+            get_top_packages(client, limiter, min, max, http).await
+        }
+        PackageSelection::Names(ref names) => Ok(names.clone()),
+    }
+}
+
+/// Resolves the packages [`download_packages`] would download, without touching the disk.
+#[tracing::instrument(name = "planning-downloads", skip(selection, http))]
+pub async fn plan_downloads(
+    selection: &PackageSelection,
+    php_version: Option<&str>,
+    min_downloads: u64,
+    http: HttpOptions<'_>,
+) -> Result<Vec<PlannedDownload>> {
+    let client = build_client(http)?;
+    let limiter = RateLimiter::new(http.requests_per_second);
+    let retry = http.retry;
+
+    let packages = list_packages(&client, &limiter, selection, http).await?;
+
+    let results: Vec<_> = stream::iter(packages)
+        .map(|package_name| {
+            let client = &client;
+            let limiter = &limiter;
+            async move {
+                let downloads =
+                    total_downloads(client, limiter, http, &package_name, min_downloads, None)
+                        .await;
+                let resolved = match downloads {
+                    Ok(Some(downloads)) if downloads < min_downloads => {
+                        tracing::debug!(
+                            "Skipping {}: {} downloads is below the minimum",
+                            package_name,
+                            downloads
+                        );
+                        Ok(None)
+                    }
+                    Ok(_) => {
+                        let description = format!("Resolving {}", package_name);
+                        retry_with_backoff(retry, &description, || {
+                            resolve_package(client, limiter, http, &package_name, php_version)
+                        })
+                        .await
+                    }
+                    Err(e) => Err(e),
+                };
+
+                (package_name, resolved)
+            }
+        })
+        .buffered(http.metadata_concurrency)
+        .collect()
+        .await;
+
+    let mut planned = Vec::new();
+    for (package_name, resolved) in results {
+        match resolved {
+            Ok(Some(resolved)) => planned.push(PlannedDownload {
+                package: resolved.name,
+                version: resolved.version,
+                url: resolved.dist.url,
+            }),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to resolve {package_name}: {e:#}"),
+        }
+    }
+
+    Ok(planned)
 }
 
-#[tracing::instrument(name = "downloading-packages")]
+/// Downloads the archives of the selected packages to `target_dir`, sending the name of
+/// each package to `downloaded` as soon as its archive is complete, e.g. to extract and
+/// analyze it while the others are still downloading.
+#[tracing::instrument(name = "downloading-packages", skip(selection, http, downloaded))]
 pub async fn download_packages(
     target_dir: PathBuf,
-    min: usize,
-    max: usize,
-) -> Result<(usize, usize)> {
+    selection: &PackageSelection,
+    php_version: Option<&str>,
+    min_downloads: u64,
+    http: HttpOptions<'_>,
+    manifest: &mut DownloadManifest,
+    downloaded: Option<&UnboundedSender<String>>,
+) -> Result<(Vec<ManifestEntry>, Vec<DownloadFailure>)> {
     fs::create_dir_all(target_dir.join("zipballs"))
         .context("Failed to create zipballs directory")?;
 
-    let client = Client::builder()
-        .user_agent("keyword-impact-analyzer/1.0.0")
-        .build()
-        .context("Failed to create HTTP client")?;
+    let client = build_client(http)?;
 
-    let packages = get_top_packages(&client, min, max).await?;
+    // Every concurrent download contends on the same limiter.
+    let limiter = Arc::new(RateLimiter::new(http.requests_per_second));
+    let retry = http.retry;
 
-    let mut successful = 0;
-    let mut failed = 0;
+    let mut packages = list_packages(&client, &limiter, selection, http).await?;
 
-    let results: Vec<_> = stream::iter(packages)
+    let total = packages.len();
+    packages.retain(|package_name| !manifest.contains(package_name));
+    if packages.len() < total {
+        tracing::info!(
+            "Resuming: {} packages already downloaded and extracted according to {:?}",
+            total - packages.len(),
+            manifest.path()
+        );
+    }
+
+    let mut resolved_packages = Vec::new();
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    let mut below_min_downloads = 0;
+    let mut failures = Vec::new();
+
+    // Metadata is small and quick to fetch, so all of it is resolved first with more
+    // requests in flight, leaving the slow archive downloads to a second pass.
+    let progress = progress::start(packages.len());
+    let resolutions: Vec<_> = stream::iter(packages)
         .map(|package_name| {
             let client = client.clone();
+            let limiter = Arc::clone(&limiter);
+            let progress = progress.clone();
+            let cached_downloads = manifest.total_downloads(&package_name);
+            async move {
+                let downloads = total_downloads(
+                    &client,
+                    &limiter,
+                    http,
+                    &package_name,
+                    min_downloads,
+                    cached_downloads,
+                )
+                .await;
+
+                let (downloads, result) = match downloads {
+                    Ok(Some(downloads)) if downloads < min_downloads => (Some(downloads), Ok(None)),
+                    Ok(downloads) => {
+                        let description = format!("Resolving {}", package_name);
+                        let resolution = retry_with_backoff(retry, &description, || {
+                            resolve_package(&client, &limiter, http, &package_name, php_version)
+                        });
+
+                        (downloads, resolution.await)
+                    }
+                    Err(e) => (None, Err(e)),
+                };
+                progress::advance(&progress);
+
+                (package_name, downloads, result)
+            }
+        })
+        .buffer_unordered(http.metadata_concurrency)
+        .collect()
+        .await;
+
+    let mut download_totals = Vec::new();
+    for (package_name, downloads, result) in resolutions {
+        match result {
+            Ok(Some(resolved)) => resolved_packages.push(resolved),
+            Ok(None) if downloads.is_some_and(|downloads| downloads < min_downloads) => {
+                below_min_downloads += 1;
+            }
+            Ok(None) => skipped += 1,
+            Err(e) => failures.push(DownloadFailure {
+                package: package_name.clone(),
+                reason: format!("Failed to resolve: {e:#}"),
+            }),
+        }
+
+        if let Some(downloads) = downloads {
+            download_totals.push((package_name, downloads));
+        }
+    }
+
+    if !download_totals.is_empty() {
+        manifest.record_total_downloads(download_totals)?;
+    }
+
+    let progress = progress::start(resolved_packages.len());
+    let results: Vec<_> = stream::iter(resolved_packages)
+        .map(|resolved| {
+            let client = client.clone();
+            let limiter = Arc::clone(&limiter);
             let target_dir = target_dir.clone();
+            let progress = progress.clone();
             async move {
-                match download_package(&client, &package_name, &target_dir).await {
-                    Ok(_) => Ok(()),
-                    Err(e) => Err((package_name, e)),
+                let description = format!("Downloading {}", resolved.name);
+                let download = retry_with_backoff(retry, &description, || {
+                    download_dist(&client, &limiter, http, &resolved, &target_dir)
+                });
+
+                let result = download.await;
+                progress::advance(&progress);
+
+                if let (Ok(_), Some(downloaded)) = (&result, downloaded) {
+                    // Only fails once nobody listens anymore, which is up to the receiver.
+                    let _ = downloaded.send(resolved.name.clone());
                 }
+
+                result.map_err(|e| (resolved.name, e))
             }
         })
-        .buffer_unordered(MAX_CONCURRENT_DOWNLOADS)
+        .buffer_unordered(http.concurrency)
         .collect()
         .await;
 
     for result in results {
         match result {
-            Ok(_) => successful += 1,
-            Err((package_name, e)) => {
-                tracing::warn!("Failed to download {package_name}: {e}");
+            Ok(entry) => entries.push(entry),
+            Err((package, e)) => failures.push(DownloadFailure {
+                package,
+                reason: format!("{e:#}"),
+            }),
+        }
+    }
 
-                failed += 1;
-            }
+    if let Some(php_version) = php_version {
+        tracing::info!(
+            "Skipped {} packages not compatible with PHP {}",
+            skipped,
+            php_version
+        );
+    }
+
+    if min_downloads > 0 {
+        tracing::info!(
+            "Skipped {} packages with fewer than {} downloads",
+            below_min_downloads,
+            min_downloads
+        );
+    }
+
+    Ok((entries, failures))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use flate2::write::ZlibEncoder;
+
+    use super::*;
+
+    fn http_options(packagist: &PackagistConfig) -> HttpOptions<'_> {
+        HttpOptions {
+            headers: &[],
+            user_agent: DEFAULT_USER_AGENT,
+            packagist,
+            retry: RetryPolicy {
+                max_retries: 0,
+                initial_delay: Duration::ZERO,
+            },
+            requests_per_second: 1000,
+            concurrency: 1,
+            metadata_concurrency: 1,
+            parallel_pages: 1,
+            verify_checksums: true,
+            version_strategy: VersionStrategy::LatestStable,
         }
     }
 
-    Ok((successful, failed))
+    /// Versions as Packagist's p2 metadata lists them, latest first.
+    fn versions(names: &[&str]) -> Vec<VersionInfo> {
+        names
+            .iter()
+            .map(|name| VersionInfo {
+                version: name.to_string(),
+                dist: None,
+                require: None,
+            })
+            .collect()
+    }
+
+    fn selected(strategy: VersionStrategy, names: &[&str]) -> String {
+        let versions = versions(names);
+
+        versions[strategy.select(&versions, "acme/tool")]
+            .version
+            .clone()
+    }
+
+    #[test]
+    fn version_strategies_select_from_latest_first_versions() {
+        let names = ["3.0.0-RC1", "2.1.0", "2.0.0", "1.0.0", "1.0.0-beta1"];
+
+        assert_eq!(selected(VersionStrategy::Latest, &names), "3.0.0-RC1");
+        assert_eq!(selected(VersionStrategy::LatestStable, &names), "2.1.0");
+        assert_eq!(selected(VersionStrategy::Oldest, &names), "1.0.0-beta1");
+    }
+
+    #[test]
+    fn latest_stable_falls_back_to_the_latest_pre_release() {
+        let names = ["2.0.0-beta2", "2.0.0-beta1", "2.0.0-alpha1"];
+
+        assert_eq!(
+            selected(VersionStrategy::LatestStable, &names),
+            "2.0.0-beta2"
+        );
+    }
+
+    #[test]
+    fn dev_branches_are_only_selected_without_releases() {
+        let names = ["dev-main", "2.x-dev", "1.0.0", "dev-legacy"];
+        assert_eq!(selected(VersionStrategy::Latest, &names), "1.0.0");
+        assert_eq!(selected(VersionStrategy::Oldest, &names), "1.0.0");
+
+        let names = ["dev-feature", "2.x-dev", "dev-master"];
+        assert_eq!(
+            selected(VersionStrategy::LatestStable, &names),
+            "dev-master"
+        );
+
+        let names = ["dev-main", "2.x-dev", "1.x-dev"];
+        assert_eq!(selected(VersionStrategy::LatestStable, &names), "dev-main");
+        assert_eq!(selected(VersionStrategy::Oldest, &names), "dev-main");
+    }
+
+    #[tokio::test]
+    async fn missing_package_fails_with_the_http_status() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/p2/acme/missing.json")
+            .with_status(404)
+            .with_body("Not Found")
+            .create_async()
+            .await;
+
+        let packagist = PackagistConfig::new(Some(&server.url()), None, None, None);
+        let http = http_options(&packagist);
+        let client = build_client(http).unwrap();
+        let limiter = RateLimiter::new(http.requests_per_second);
+
+        let error = resolve_package(&client, &limiter, http, "acme/missing", None)
+            .await
+            .unwrap_err();
+
+        mock.assert_async().await;
+        let message = format!("{:#}", error);
+        assert!(message.contains("Packagist returned an error for acme/missing"));
+        assert!(message.contains("404"));
+        assert!(!is_transient(&error));
+    }
+
+    const METADATA: &str = r#"{"packages": {"acme/tool": [
+        {"version": "2.0.0", "dist": {"url": "https://example.com/acme-tool.zip", "type": "zip"}}
+    ]}}"#;
+
+    /// Serves [`METADATA`] encoded as `encoding`, if the client accepts it, and
+    /// returns the version resolved from it.
+    async fn resolve_encoded_metadata(encoding: &str, body: Vec<u8>) -> String {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/p2/acme/tool.json")
+            .match_header(
+                "accept-encoding",
+                mockito::Matcher::Regex(format!(r"\b{}\b", encoding)),
+            )
+            .with_header("content-encoding", encoding)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let packagist = PackagistConfig::new(Some(&server.url()), None, None, None);
+        let http = http_options(&packagist);
+        let client = build_client(http).unwrap();
+        let limiter = RateLimiter::new(http.requests_per_second);
+
+        let resolved = resolve_package(&client, &limiter, http, "acme/tool", None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        mock.assert_async().await;
+        resolved.version
+    }
+
+    #[tokio::test]
+    async fn gzip_compressed_metadata_is_decoded() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(METADATA.as_bytes()).unwrap();
+
+        let version = resolve_encoded_metadata("gzip", encoder.finish().unwrap()).await;
+
+        assert_eq!(version, "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn brotli_compressed_metadata_is_decoded() {
+        let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+        encoder.write_all(METADATA.as_bytes()).unwrap();
+
+        let version = resolve_encoded_metadata("br", encoder.into_inner()).await;
+
+        assert_eq!(version, "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn deflate_compressed_metadata_is_decoded() {
+        // HTTP's `deflate` is the zlib format, not raw deflate.
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(METADATA.as_bytes()).unwrap();
+
+        let version = resolve_encoded_metadata("deflate", encoder.finish().unwrap()).await;
+
+        assert_eq!(version, "2.0.0");
+    }
 }
@@ -1,38 +1,123 @@
 use std::fs;
 use std::io;
+use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::Result;
+use flate2::read::GzDecoder;
 use rayon::prelude::*;
 
+use crate::progress;
+
+/// Archive formats Packagist distributes packages in.
+const ARCHIVE_EXTENSIONS: &[&str] = &[".zip", ".tar.gz", ".tgz"];
+/// Archives are stored at `zipballs/<vendor>/<package>`, so anything much deeper is a
+/// stray directory tree or a symlink loop.
+const MAX_ZIPBALL_DEPTH: usize = 20;
+
+fn extract_archive(archive_path: &Path, extract_to: &Path) -> Result<()> {
+    let name = archive_path.to_string_lossy();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        extract_tarball(archive_path, extract_to)
+    } else {
+        extract_zip(archive_path, extract_to)
+    }
+}
+
+#[tracing::instrument(name = "extracting-tarball", skip(extract_to))]
+fn extract_tarball(tar_path: &Path, extract_to: &Path) -> Result<()> {
+    let file = fs::File::open(tar_path).context("Failed to open tarball")?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    extract_via_temp_dir(extract_to, |temp_dir| {
+        archive.unpack(temp_dir).context("Failed to unpack tarball")
+    })
+}
+
 #[tracing::instrument(name = "extracting-zip", skip(extract_to))]
 fn extract_zip(zip_path: &Path, extract_to: &Path) -> Result<()> {
     let file = fs::File::open(zip_path).context("Failed to open zip file")?;
     let mut archive = zip::ZipArchive::new(file).context("Failed to read zip archive")?;
 
+    extract_via_temp_dir(extract_to, |temp_dir| {
+        for i in 0..archive.len() {
+            let mut file = archive
+                .by_index(i)
+                .context("Failed to get file from archive")?;
+            let Some(relative_path) = sanitize_entry_path(file.name()) else {
+                tracing::warn!(
+                    "Skipping zip entry {:?} in {:?}: it escapes the extraction directory",
+                    file.name(),
+                    zip_path
+                );
+                continue;
+            };
+            let outpath = temp_dir.join(relative_path);
+
+            if file.is_dir() {
+                fs::create_dir_all(&outpath).context("Failed to create directory")?;
+            } else {
+                if let Some(parent) = outpath.parent() {
+                    fs::create_dir_all(parent).context("Failed to create parent directory")?;
+                }
+                let mut outfile =
+                    fs::File::create(&outpath).context("Failed to create output file")?;
+                io::copy(&mut file, &mut outfile).context("Failed to copy file contents")?;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Unpacks an archive into a temporary directory next to `extract_to` and moves it
+/// into place, removing the temporary directory again if unpacking fails so that
+/// a corrupt or truncated archive leaves nothing behind.
+fn extract_via_temp_dir(extract_to: &Path, unpack: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
     let temp_dir = extract_to.with_extension("tmp");
+    if temp_dir.exists() {
+        // Left behind by an interrupted run; unpacking over it would mix in stale files.
+        fs::remove_dir_all(&temp_dir).context("Failed to remove stale temp directory")?;
+    }
     fs::create_dir_all(&temp_dir).context("Failed to create temp directory")?;
 
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .context("Failed to get file from archive")?;
-        let outpath = temp_dir.join(file.name());
-
-        if file.is_dir() {
-            fs::create_dir_all(&outpath).context("Failed to create directory")?;
-        } else {
-            if let Some(parent) = outpath.parent() {
-                fs::create_dir_all(parent).context("Failed to create parent directory")?;
-            }
-            let mut outfile = fs::File::create(&outpath).context("Failed to create output file")?;
-            io::copy(&mut file, &mut outfile).context("Failed to copy file contents")?;
+    if let Err(e) = unpack(&temp_dir) {
+        if let Err(cleanup_error) = fs::remove_dir_all(&temp_dir) {
+            tracing::warn!("Failed to remove {:?}: {}", temp_dir, cleanup_error);
         }
+
+        return Err(e);
     }
 
-    let entries: Vec<_> = fs::read_dir(&temp_dir)
+    move_into_place(&temp_dir, extract_to)
+}
+
+/// Turns an archive entry name into a path relative to the extraction directory,
+/// dropping leading `/` and `./`, or returns `None` if it would escape it via `..`.
+///
+/// Zips created on Windows may use `\` as separator, which would otherwise end up
+/// in file names on Unix.
+fn sanitize_entry_path(name: &str) -> Option<PathBuf> {
+    let name = name.replace('\\', "/");
+
+    let mut path = PathBuf::new();
+    for component in Path::new(&name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+            Component::ParentDir => return None,
+        }
+    }
+
+    Some(path)
+}
+
+/// Moves an extracted archive to `extract_to`, flattening the single root
+/// directory most archives wrap their contents in.
+fn move_into_place(temp_dir: &Path, extract_to: &Path) -> Result<()> {
+    let entries: Vec<_> = fs::read_dir(temp_dir)
         .context("Failed to read temp directory")?
         .filter_map(|e| e.ok())
         .collect();
@@ -40,9 +125,9 @@ fn extract_zip(zip_path: &Path, extract_to: &Path) -> Result<()> {
     if entries.len() == 1 && entries[0].path().is_dir() {
         let subdir = &entries[0].path();
         fs::rename(subdir, extract_to).context("Failed to move subdirectory")?;
-        fs::remove_dir(&temp_dir).context("Failed to remove temp directory")?;
+        fs::remove_dir(temp_dir).context("Failed to remove temp directory")?;
     } else {
-        fs::rename(&temp_dir, extract_to).context("Failed to rename temp directory")?;
+        fs::rename(temp_dir, extract_to).context("Failed to rename temp directory")?;
     }
 
     Ok(())
@@ -60,6 +145,7 @@ pub fn extract_packages(target_dir: PathBuf) -> Result<usize> {
 
     tracing::info!("Extracting {} packages...", zip_files.len());
 
+    let progress = progress::start(zip_files.len());
     let results: Vec<_> = zip_files
         .par_iter()
         .map(|zip_path| {
@@ -70,21 +156,9 @@ pub fn extract_packages(target_dir: PathBuf) -> Result<usize> {
                 .and_then(|p| p.to_str())
                 .unwrap_or("");
 
-            let extract_dir = sources_dir.join(package_name);
-
-            if extract_dir.exists() {
-                tracing::debug!("Package {} already extracted, skipping", package_name);
-                return Ok(());
-            }
-
-            tracing::trace!("Extracting {} to {:?}", package_name, extract_dir);
-            extract_zip(zip_path, &extract_dir).with_context(|| {
-                format!(
-                    "Failed to extract package {} from {:?}",
-                    package_name, zip_path
-                )
-            })
+            extract_package(&sources_dir, package_name, zip_path)
         })
+        .inspect(|_| progress::advance(&progress))
         .collect();
 
     let mut successful = 0;
@@ -112,21 +186,240 @@ pub fn extract_packages(target_dir: PathBuf) -> Result<usize> {
     Ok(successful)
 }
 
+/// Extracts the archives of the given `vendor/package` names, e.g. as soon as they are
+/// downloaded, and returns the packages whose sources are now in place, including ones
+/// extracted before.
+#[tracing::instrument(name = "extracting-listed-packages", skip(packages))]
+pub fn extract_listed_packages(target_dir: &Path, packages: &[String]) -> Vec<String> {
+    let zipballs_dir = target_dir.join("zipballs");
+    let sources_dir = target_dir.join("sources");
+
+    packages
+        .par_iter()
+        .filter(|package_name| {
+            let mut zip_files = Vec::new();
+            let result = collect_zip_files(&zipballs_dir.join(package_name), &mut zip_files)
+                .and_then(|_| match zip_files.first() {
+                    Some(zip_path) => extract_package(&sources_dir, package_name, zip_path),
+                    None => Ok(()),
+                });
+
+            if let Err(e) = result {
+                tracing::warn!("{}", e);
+            }
+
+            sources_dir.join(package_name).exists()
+        })
+        .cloned()
+        .collect()
+}
+
+fn extract_package(sources_dir: &Path, package_name: &str, zip_path: &Path) -> Result<()> {
+    let extract_dir = sources_dir.join(package_name);
+
+    if extract_dir.exists() {
+        tracing::debug!("Package {} already extracted, skipping", package_name);
+        return Ok(());
+    }
+
+    tracing::trace!("Extracting {} to {:?}", package_name, extract_dir);
+    extract_archive(zip_path, &extract_dir).with_context(|| {
+        format!(
+            "Failed to extract package {} from {:?}",
+            package_name, zip_path
+        )
+    })
+}
+
+/// Walks `dir` with an explicit stack rather than recursion, skipping directories
+/// deeper than [`MAX_ZIPBALL_DEPTH`].
 fn collect_zip_files(dir: &Path, zip_files: &mut Vec<PathBuf>) -> Result<()> {
     if !dir.exists() {
         return Ok(());
     }
 
-    for entry in fs::read_dir(dir).context("Failed to read directory")? {
-        let entry = entry.context("Failed to read directory entry")?;
-        let path = entry.path();
+    let mut directories = vec![(dir.to_path_buf(), 0)];
+    while let Some((dir, depth)) = directories.pop() {
+        for entry in fs::read_dir(&dir).context("Failed to read directory")? {
+            let entry = entry.context("Failed to read directory entry")?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                if depth >= MAX_ZIPBALL_DEPTH {
+                    tracing::warn!(
+                        "Skipping {:?}: more than {} directories deep",
+                        path,
+                        MAX_ZIPBALL_DEPTH
+                    );
+                    continue;
+                }
 
-        if path.is_dir() {
-            collect_zip_files(&path, zip_files)?;
-        } else if path.extension().and_then(|s| s.to_str()) == Some("zip") {
-            zip_files.push(path);
+                directories.push((path, depth + 1));
+            } else if ARCHIVE_EXTENSIONS
+                .iter()
+                .any(|extension| path.to_string_lossy().ends_with(extension))
+            {
+                zip_files.push(path);
+            }
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, contents) in entries {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn entry_paths_are_kept_inside_the_extraction_directory() {
+        assert_eq!(sanitize_entry_path("../evil.txt"), None);
+        assert_eq!(sanitize_entry_path("pkg/../../evil.txt"), None);
+        assert_eq!(
+            sanitize_entry_path("/etc/passwd"),
+            Some(PathBuf::from("etc/passwd"))
+        );
+        assert_eq!(
+            sanitize_entry_path("./pkg/src/A.php"),
+            Some(PathBuf::from("pkg/src/A.php"))
+        );
+        assert_eq!(
+            sanitize_entry_path("..\\evil.txt"),
+            None,
+            "`\\` separators must not hide a `..`"
+        );
+    }
+
+    #[test]
+    fn zip_entries_escaping_the_extraction_directory_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("package.zip");
+        let vendor_dir = dir.path().join("sources").join("acme");
+        let extract_to = vendor_dir.join("tool");
+        fs::create_dir_all(&vendor_dir).unwrap();
+        write_zip(
+            &zip_path,
+            &[("../evil.txt", "evil"), ("tool/src/Ok.php", "<?php")],
+        );
+
+        extract_zip(&zip_path, &extract_to).unwrap();
+
+        assert!(extract_to.join("src").join("Ok.php").is_file());
+        assert!(!vendor_dir.join("evil.txt").exists());
+        assert!(!extract_to.join("evil.txt").exists());
+    }
+
+    #[test]
+    fn backslash_separated_entries_extract_to_nested_directories() {
+        assert_eq!(
+            sanitize_entry_path("tool\\src\\A.php"),
+            Some(PathBuf::from("tool/src/A.php"))
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("package.zip");
+        let extract_to = dir.path().join("tool");
+        write_zip(
+            &zip_path,
+            &[
+                ("tool\\composer.json", "{}"),
+                ("tool\\src\\Console\\Command.php", "<?php"),
+            ],
+        );
+
+        extract_zip(&zip_path, &extract_to).unwrap();
+
+        assert!(extract_to.join("composer.json").is_file());
+        assert!(
+            extract_to
+                .join("src")
+                .join("Console")
+                .join("Command.php")
+                .is_file()
+        );
+
+        let mut names: Vec<_> = fs::read_dir(&extract_to)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["composer.json", "src"]);
+    }
+
+    #[test]
+    fn truncated_zip_leaves_nothing_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("package.zip");
+        let extract_to = dir.path().join("tool");
+        write_zip(&zip_path, &[("tool/src/Tool.php", "<?php")]);
+        let contents = fs::read(&zip_path).unwrap();
+        fs::write(&zip_path, &contents[..contents.len() / 2]).unwrap();
+
+        assert!(extract_zip(&zip_path, &extract_to).is_err());
+
+        assert!(!extract_to.exists());
+        assert!(!extract_to.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn temp_dir_is_removed_when_unpacking_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("package.zip");
+        let extract_to = dir.path().join("tool");
+
+        let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+        let stored = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        zip.start_file("tool/src/First.php", stored).unwrap();
+        zip.write_all(b"<?php echo 'first';").unwrap();
+        zip.start_file("tool/src/Second.php", stored).unwrap();
+        zip.write_all(b"<?php echo 'intact';").unwrap();
+        zip.finish().unwrap();
+
+        // Same length, so only the checksum of the second entry no longer matches.
+        let mut contents = fs::read(&zip_path).unwrap();
+        let offset = contents
+            .windows(6)
+            .position(|window| window == b"intact")
+            .unwrap();
+        contents[offset..offset + 6].copy_from_slice(b"broken");
+        fs::write(&zip_path, contents).unwrap();
+
+        assert!(extract_zip(&zip_path, &extract_to).is_err());
+
+        assert!(!extract_to.exists());
+        assert!(!extract_to.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn deeply_nested_directories_are_not_walked_past_the_depth_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let package_dir = dir.path().join("acme").join("tool");
+        fs::create_dir_all(&package_dir).unwrap();
+        fs::write(package_dir.join("acme-tool.zip"), "").unwrap();
+
+        let mut deep_dir = dir.path().to_path_buf();
+        for level in 0..25 {
+            deep_dir.push(format!("level{}", level));
+        }
+        fs::create_dir_all(&deep_dir).unwrap();
+        fs::write(deep_dir.join("too-deep.zip"), "").unwrap();
+
+        let mut zip_files = Vec::new();
+        collect_zip_files(dir.path(), &mut zip_files).unwrap();
+
+        assert_eq!(zip_files, [package_dir.join("acme-tool.zip")]);
+    }
+}
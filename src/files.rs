@@ -1,39 +1,191 @@
 use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs;
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use anyhow::Context;
+use anyhow::Result;
+use globset::Glob;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
 use rayon::Scope;
 use rayon::prelude::*;
+use serde::Deserialize;
 
 use mago_database::file::File;
 use mago_database::file::FileType;
 
+use crate::php_version;
+use crate::results::CustomVendor;
 use crate::results::Vendor;
 
+/// Always analyzed; see [`php_extensions`] for the opt-in ones.
 const PHP_EXTENSION: &[&str] = &["php", "php7", "php8"];
+const TEST_DIRECTORIES: &[&str] = &["test", "tests", "spec", "specs", "fixtures"];
 
-#[tracing::instrument(name = "reading-file", skip(sources_canonical))]
-pub fn read_file(file: &Path, sources_canonical: &Path) -> Option<(Vendor, File)> {
+#[derive(Debug, Deserialize)]
+struct ComposerJson {
+    name: Option<String>,
+    /// Left untyped, since an empty `require` is sometimes written as `[]`.
+    require: Option<serde_json::Value>,
+}
+
+/// Package names of every `sources/<vendor>/<package>` directory, taken from the
+/// package's `composer.json` when present and from the directory path otherwise.
+#[derive(Debug)]
+pub struct PackageNames {
+    sources_canonical: PathBuf,
+    names: HashMap<String, String>,
+    custom_vendors: Vec<CustomVendor>,
+}
+
+impl PackageNames {
+    #[tracing::instrument(name = "loading-package-names")]
+    pub fn load(sources_canonical: &Path, custom_vendors: &[CustomVendor]) -> Self {
+        let mut names = HashMap::new();
+
+        for vendor_dir in fs::read_dir(sources_canonical)
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            for package_dir in fs::read_dir(vendor_dir.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+            {
+                let path = package_dir.path();
+                let Some(directory_name) = Self::directory_name(sources_canonical, &path) else {
+                    continue;
+                };
+
+                if let Some(composer_name) = Self::composer_name(&path) {
+                    names.insert(directory_name, composer_name);
+                }
+            }
+        }
+
+        tracing::debug!("Loaded {} package names from composer.json", names.len());
+
+        Self {
+            sources_canonical: sources_canonical.to_path_buf(),
+            names,
+            custom_vendors: custom_vendors.to_vec(),
+        }
+    }
+
+    /// Adds the names of `vendor/package` directories extracted after [`PackageNames::load`].
+    pub fn load_packages(&mut self, packages: &[String]) {
+        for directory_name in packages {
+            let path = self.sources_canonical.join(directory_name);
+            if let Some(composer_name) = Self::composer_name(&path) {
+                self.names.insert(directory_name.clone(), composer_name);
+            }
+        }
+    }
+
+    /// Returns the `vendor/package` name of the package containing `file`.
+    pub fn package_of(&self, file: &Path) -> Option<String> {
+        let directory_name = Self::directory_name(&self.sources_canonical, file)?;
+
+        match self.names.get(&directory_name) {
+            Some(name) => Some(name.clone()),
+            None => Some(directory_name),
+        }
+    }
+
+    /// Returns the `vendor/package` directory containing `file`, which may differ
+    /// from the package name in its `composer.json`.
+    pub fn directory_of(&self, file: &Path) -> Option<String> {
+        Self::directory_name(&self.sources_canonical, file)
+    }
+
+    pub fn vendor_of(&self, file: &Path) -> Vendor {
+        self.package_of(file)
+            .map(|package_name| Vendor::from_package(&package_name, &self.custom_vendors))
+            .unwrap_or(Vendor::Other)
+    }
+
+    fn composer_name(package_dir: &Path) -> Option<String> {
+        fs::read(package_dir.join("composer.json"))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<ComposerJson>(&bytes).ok())
+            .and_then(|composer| composer.name)
+            .map(|name| name.to_lowercase())
+    }
+
+    fn directory_name(sources_canonical: &Path, path: &Path) -> Option<String> {
+        let mut components = path.strip_prefix(sources_canonical).ok()?.components();
+        let vendor = components.next()?.as_os_str().to_str()?;
+        let package = components.next()?.as_os_str().to_str()?;
+
+        Some(format!("{}/{}", vendor, package))
+    }
+}
+
+/// Returns the `vendor/package` directories below `sources_dir` whose `composer.json`
+/// requires a PHP version excluding `php_version`. Packages without a `require.php`
+/// constraint are assumed to support every version.
+#[tracing::instrument(name = "checking-php-versions")]
+pub fn packages_incompatible_with(
+    sources_dir: &Path,
+    php_version: &str,
+) -> Result<BTreeSet<String>> {
+    // Fails once for an invalid version, instead of once per package.
+    php_version::satisfies("*", php_version)?;
+
+    let mut incompatible = BTreeSet::new();
+    for vendor_dir in fs::read_dir(sources_dir).into_iter().flatten().flatten() {
+        for package_dir in fs::read_dir(vendor_dir.path())
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let path = package_dir.path();
+            let constraint = fs::read(path.join("composer.json"))
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<ComposerJson>(&bytes).ok())
+                .and_then(|composer| Some(composer.require?.get("php")?.as_str()?.to_string()));
+
+            let Some(constraint) = constraint else {
+                continue;
+            };
+
+            let compatible = php_version::satisfies(&constraint, php_version).unwrap_or_else(|e| {
+                tracing::warn!("Skipping {:?}: {}", path, e);
+                false
+            });
+
+            if !compatible {
+                incompatible.insert(format!(
+                    "{}/{}",
+                    vendor_dir.file_name().to_string_lossy(),
+                    package_dir.file_name().to_string_lossy()
+                ));
+            }
+        }
+    }
+
+    Ok(incompatible)
+}
+
+/// Reads a file to analyze, and whether it is not valid UTF-8, in which case invalid
+/// bytes are replaced and a keyword spanning them is missed.
+#[tracing::instrument(name = "reading-file", skip(package_names))]
+pub fn read_file(file: &Path, package_names: &PackageNames) -> Option<(Vendor, File, bool)> {
     let bytes = fs::read(file).ok()?;
-    let contents = match str::from_utf8(&bytes) {
-        Ok(s) => s.to_string(),
-        Err(_) => String::from_utf8_lossy(&bytes).into_owned(),
+    let (contents, is_non_utf8) = match str::from_utf8(&bytes) {
+        Ok(s) => (s.to_string(), false),
+        Err(_) => (String::from_utf8_lossy(&bytes).into_owned(), true),
     };
 
-    let vendor = file
-        .strip_prefix(sources_canonical)
-        .ok()
-        .and_then(|p| {
-            let mut components = p.components();
-            let vendor = components.next()?.as_os_str().to_str()?;
-            let package = components.next()?.as_os_str().to_str()?;
-            let package_name = format!("{}/{}", vendor, package);
-            Some(Vendor::from_package(&package_name))
-        })
-        .unwrap_or(Vendor::Other);
+    let vendor = package_names.vendor_of(file);
 
     Some((
         vendor,
@@ -43,41 +195,450 @@ pub fn read_file(file: &Path, sources_canonical: &Path) -> Option<(Vendor, File)
             Some(file.to_path_buf()),
             Cow::Owned(contents),
         ),
+        is_non_utf8,
     ))
 }
 
-#[tracing::instrument(name = "walking-files")]
-pub fn walk_files(base_path: &Path) -> impl ParallelIterator<Item = PathBuf> + use<> {
-    let entries = Arc::new(Mutex::new(Vec::new()));
+/// Includes or excludes packages by vendor, using their `sources/<vendor>` directory.
+#[derive(Debug, Clone, Default)]
+pub struct VendorFilter {
+    vendors: Vec<String>,
+    excluded_vendors: Vec<String>,
+}
+
+impl VendorFilter {
+    pub fn new(vendors: &[String], excluded_vendors: &[String]) -> Self {
+        Self {
+            vendors: vendors.iter().map(|v| v.to_lowercase()).collect(),
+            excluded_vendors: excluded_vendors.iter().map(|v| v.to_lowercase()).collect(),
+        }
+    }
 
-    let base_path = base_path.to_owned();
-    let move_entries = entries.clone();
-    rayon::scope(move |s| s.spawn(move |s1| read_dir(move_entries, s1, base_path)));
+    /// An explicitly included vendor wins over the same vendor being excluded.
+    fn allows(&self, vendor_directory: &Path) -> bool {
+        let Some(name) = vendor_directory.file_name().and_then(|name| name.to_str()) else {
+            return self.vendors.is_empty();
+        };
+
+        let name = name.to_lowercase();
+        if self.vendors.contains(&name) {
+            return true;
+        }
+
+        self.vendors.is_empty() && !self.excluded_vendors.contains(&name)
+    }
+}
+
+/// Returns the extensions of files to analyze: the PHP ones, plus templates
+/// (`.phtml`) and legacy includes (`.inc`) on request.
+pub fn php_extensions(include_phtml: bool, include_inc: bool) -> Vec<&'static str> {
+    let mut extensions = PHP_EXTENSION.to_vec();
+    if include_phtml {
+        extensions.push("phtml");
+    }
+    if include_inc {
+        extensions.push("inc");
+    }
 
-    let entries = Arc::try_unwrap(entries).unwrap().into_inner().unwrap();
-    entries.into_par_iter()
+    extensions
 }
 
-#[tracing::instrument(name = "reading-directory", skip(entries, s))]
-fn read_dir(entries: Arc<Mutex<Vec<PathBuf>>>, s: &Scope<'_>, base_path: PathBuf) {
-    for entry in fs::read_dir(base_path).unwrap() {
-        let entry = entry.unwrap();
+/// Includes files by extension, and includes or excludes them by glob patterns
+/// (e.g. `*/tests/*`) matched against their path relative to the sources directory.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    extensions: Vec<&'static str>,
+    /// `None` includes every file.
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    /// `vendor/package` directories to analyze; `None` includes every package.
+    packages: Option<HashSet<String>>,
+    /// `vendor/package` directories to leave out, e.g. because a baseline report covers them.
+    skipped_packages: HashSet<String>,
+}
+
+impl PathFilter {
+    pub fn new(
+        extensions: Vec<&'static str>,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<Self> {
+        let include = if include.is_empty() {
+            None
+        } else {
+            Some(Self::glob_set(include)?)
+        };
+
+        Ok(Self {
+            extensions,
+            include,
+            exclude: Self::glob_set(exclude)?,
+            packages: None,
+            skipped_packages: HashSet::new(),
+        })
+    }
+
+    /// Restricts the analysis to the given `vendor/package` names, e.g. so that
+    /// packages downloaded by earlier runs are left out.
+    pub fn only_packages(mut self, packages: &[String]) -> Self {
+        self.packages = Some(packages.iter().map(|name| name.to_lowercase()).collect());
+        self
+    }
+
+    /// Replaces the extensions of analyzed files, e.g. to add `phtml`.
+    pub fn extensions(mut self, extensions: Vec<&'static str>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    pub fn skip_packages(mut self, packages: &BTreeSet<String>) -> Self {
+        self.skipped_packages
+            .extend(packages.iter().map(|name| name.to_lowercase()));
+        self
+    }
+
+    fn glob_set(patterns: &[String]) -> Result<GlobSet> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern)
+                .with_context(|| format!("Invalid glob pattern {:?}", pattern))?;
+            builder.add(glob);
+        }
+
+        builder.build().context("Failed to compile glob patterns")
+    }
+
+    /// Directories are matched with a trailing separator, so that `*/tests/*`
+    /// skips the whole `tests` directory instead of each of its files.
+    fn excludes_directory(&self, relative_path: &Path) -> bool {
+        let mut components = relative_path.components();
+        if let (Some(vendor), Some(package), None) =
+            (components.next(), components.next(), components.next())
+        {
+            let name = format!(
+                "{}/{}",
+                vendor.as_os_str().to_string_lossy(),
+                package.as_os_str().to_string_lossy()
+            )
+            .to_lowercase();
+
+            if self.skipped_packages.contains(&name)
+                || self
+                    .packages
+                    .as_ref()
+                    .is_some_and(|packages| !packages.contains(&name))
+            {
+                return true;
+            }
+        }
+
+        self.exclude.is_match(relative_path.join(""))
+    }
+
+    fn allows_file(&self, relative_path: &Path) -> bool {
+        has_php_extension(relative_path, &self.extensions)
+            && self
+                .include
+                .as_ref()
+                .is_none_or(|include| include.is_match(relative_path))
+            && !self.exclude.is_match(relative_path)
+    }
+}
+
+/// Includes every file with one of the default PHP extensions.
+impl Default for PathFilter {
+    fn default() -> Self {
+        Self {
+            extensions: PHP_EXTENSION.to_vec(),
+            include: None,
+            exclude: GlobSet::empty(),
+            packages: None,
+            skipped_packages: HashSet::new(),
+        }
+    }
+}
+
+/// A filesystem error encountered while walking the sources directory.
+#[derive(Debug)]
+pub struct WalkError {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+impl WalkError {
+    fn new(path: PathBuf, error: io::Error) -> Self {
+        Self {
+            path,
+            error: error.to_string(),
+        }
+    }
+}
+
+/// Configures a walk of a sources directory, e.g. a temporary one, with the files
+/// and vendors to include. [`walk_files`] is the shorthand used by the analysis.
+#[derive(Debug, Clone, Default)]
+pub struct WalkBuilder {
+    base_path: PathBuf,
+    vendor_filter: VendorFilter,
+    path_filter: PathFilter,
+}
+
+impl WalkBuilder {
+    /// The sources directory, whose subdirectories are vendors.
+    pub fn base_path(mut self, base_path: &Path) -> Self {
+        self.base_path = base_path.to_owned();
+        self
+    }
+
+    pub fn vendor_filter(mut self, vendor_filter: VendorFilter) -> Self {
+        self.vendor_filter = vendor_filter;
+        self
+    }
+
+    /// Extensions, glob patterns and packages of the files to include.
+    pub fn path_filter(mut self, path_filter: PathFilter) -> Self {
+        self.path_filter = path_filter;
+        self
+    }
+
+    /// Walks the directory in the current rayon pool, returning the included files
+    /// and the errors of the files and directories that could not be read.
+    #[tracing::instrument(name = "walking-files", skip(self), fields(base_path = ?self.base_path))]
+    pub fn build(
+        self,
+    ) -> (
+        impl ParallelIterator<Item = PathBuf> + use<>,
+        Vec<WalkError>,
+    ) {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let errors = Arc::new(Mutex::new(Vec::new()));
+
+        let base_path = self.base_path;
+        let vendor_filter = self.vendor_filter;
+        let path_filter = Arc::new((base_path.clone(), self.path_filter));
+        let move_entries = entries.clone();
+        let move_errors = errors.clone();
+        rayon::scope(move |s| {
+            s.spawn(move |s1| {
+                read_dir(
+                    move_entries,
+                    move_errors,
+                    s1,
+                    base_path,
+                    Some(vendor_filter),
+                    path_filter,
+                )
+            })
+        });
+
+        let entries = Arc::try_unwrap(entries).unwrap().into_inner().unwrap();
+        let errors = Arc::try_unwrap(errors).unwrap().into_inner().unwrap();
+        (entries.into_par_iter(), errors)
+    }
+}
+
+pub fn walk_files(
+    base_path: &Path,
+    vendor_filter: VendorFilter,
+    path_filter: PathFilter,
+) -> (
+    impl ParallelIterator<Item = PathBuf> + use<>,
+    Vec<WalkError>,
+) {
+    WalkBuilder::default()
+        .base_path(base_path)
+        .vendor_filter(vendor_filter)
+        .path_filter(path_filter)
+        .build()
+}
+
+/// `vendor_filter` is only given for the sources directory itself, whose subdirectories are vendors.
+///
+/// `path_filter` holds the sources directory that its patterns are relative to.
+#[tracing::instrument(name = "reading-directory", skip(entries, errors, s, path_filter))]
+fn read_dir(
+    entries: Arc<Mutex<Vec<PathBuf>>>,
+    errors: Arc<Mutex<Vec<WalkError>>>,
+    s: &Scope<'_>,
+    base_path: PathBuf,
+    vendor_filter: Option<VendorFilter>,
+    path_filter: Arc<(PathBuf, PathFilter)>,
+) {
+    // Scope::spawn closures cannot return errors, so they are collected on the side.
+    let read_dir = match fs::read_dir(&base_path) {
+        Ok(read_dir) => read_dir,
+        Err(e) => {
+            errors.lock().unwrap().push(WalkError::new(base_path, e));
+            return;
+        }
+    };
+
+    for entry in read_dir {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                errors
+                    .lock()
+                    .unwrap()
+                    .push(WalkError::new(base_path.clone(), e));
+                continue;
+            }
+        };
+
         let path = entry.path();
-        let metadata = entry.metadata().unwrap();
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                errors.lock().unwrap().push(WalkError::new(path, e));
+                continue;
+            }
+        };
+
+        let (sources_path, filter) = &*path_filter;
+        let relative_path = path.strip_prefix(sources_path).unwrap_or(&path);
+
         if metadata.is_dir() {
+            if filter.excludes_directory(relative_path) {
+                tracing::debug!("Skipping excluded directory {:?}", path);
+                continue;
+            }
+
+            if vendor_filter
+                .as_ref()
+                .is_some_and(|vendor_filter| !vendor_filter.allows(&path))
+            {
+                tracing::debug!("Skipping vendor directory {:?}", path);
+                continue;
+            }
+
             let move_entries = entries.clone();
-            s.spawn(move |s1| read_dir(move_entries, s1, path));
-        } else if metadata.is_file() && has_php_extension(&path) {
+            let move_errors = errors.clone();
+            let move_path_filter = path_filter.clone();
+            s.spawn(move |s1| {
+                read_dir(move_entries, move_errors, s1, path, None, move_path_filter)
+            });
+        } else if metadata.is_file() && filter.allows_file(relative_path) {
             let mut locked = entries.lock().unwrap();
             locked.push(path);
         }
     }
 }
 
-fn has_php_extension(path: &Path) -> bool {
+fn has_php_extension(path: &Path, extensions: &[&str]) -> bool {
     if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        PHP_EXTENSION.contains(&ext)
+        extensions.contains(&ext)
     } else {
         false
     }
 }
+
+/// Heuristically decides whether a path (relative to the sources directory) belongs to test code.
+pub fn is_test_file(path: &Path) -> bool {
+    let in_test_directory = path.components().any(|component| {
+        component.as_os_str().to_str().is_some_and(|name| {
+            TEST_DIRECTORIES
+                .iter()
+                .any(|directory| name.eq_ignore_ascii_case(directory))
+        })
+    });
+
+    in_test_directory
+        || path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem.ends_with("Test"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources_directory() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "acme/tool/src/Tool.php",
+            "acme/tool/src/view.phtml",
+            "acme/tool/tests/ToolTest.php",
+            "acme/tool/README.md",
+            "legacy/lib/functions.inc",
+            "symfony/console/Command.php8",
+        ] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "<?php").unwrap();
+        }
+
+        dir
+    }
+
+    /// The walked files relative to `base_path`, sorted as the walk is parallel.
+    fn walked_files(builder: WalkBuilder, base_path: &Path) -> Vec<String> {
+        let (files, errors) = builder.base_path(base_path).build();
+        assert!(errors.is_empty());
+
+        let mut files: Vec<String> = files
+            .map(|file| {
+                file.strip_prefix(base_path)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn walk_includes_php_files_of_every_vendor_by_default() {
+        let dir = sources_directory();
+
+        assert_eq!(
+            walked_files(WalkBuilder::default(), dir.path()),
+            [
+                "acme/tool/src/Tool.php",
+                "acme/tool/tests/ToolTest.php",
+                "symfony/console/Command.php8",
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_applies_extensions_patterns_and_vendors() {
+        let dir = sources_directory();
+        let path_filter =
+            PathFilter::new(php_extensions(true, true), &[], &["*/tests/*".to_string()]).unwrap();
+        let builder = WalkBuilder::default()
+            .path_filter(path_filter)
+            .vendor_filter(VendorFilter::new(&[], &["Symfony".to_string()]));
+
+        assert_eq!(
+            walked_files(builder, dir.path()),
+            [
+                "acme/tool/src/Tool.php",
+                "acme/tool/src/view.phtml",
+                "legacy/lib/functions.inc",
+            ]
+        );
+
+        let builder = WalkBuilder::default()
+            .path_filter(PathFilter::default().only_packages(&["acme/tool".to_string()]))
+            .vendor_filter(VendorFilter::new(&["acme".to_string()], &[]));
+
+        assert_eq!(
+            walked_files(builder, dir.path()),
+            ["acme/tool/src/Tool.php", "acme/tool/tests/ToolTest.php"]
+        );
+    }
+
+    #[test]
+    fn walk_reports_an_unreadable_base_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+
+        let (files, errors) = WalkBuilder::default().base_path(&missing).build();
+
+        assert_eq!(files.count(), 0);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, missing);
+    }
+}
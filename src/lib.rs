@@ -0,0 +1,37 @@
+//! Analyzes how PHP packages use identifiers that a PHP RFC proposes to reserve
+//! as keywords, to estimate the impact of reserving them.
+//!
+//! [`analyze_directory`] analyzes every PHP file below a `sources/<vendor>/<package>`
+//! directory, as configured by a [`DirectoryAnalysis`], and returns an [`AnalysisReport`]
+//! with a [`KeywordResult`] per keyword.
+
+pub mod analyzer;
+pub mod cache;
+pub mod config;
+pub mod corpus_gen;
+pub mod downloader;
+pub mod extractor;
+pub mod files;
+pub mod manifest;
+pub mod match_store;
+pub mod output;
+pub mod php_version;
+pub mod progress;
+pub mod results;
+pub mod timing;
+
+pub use analyzer::AnalysisOptions;
+pub use analyzer::DirectoryAnalysis;
+pub use analyzer::analyze_directory;
+pub use analyzer::analyze_directory_streaming;
+pub use files::PathFilter;
+pub use files::VendorFilter;
+pub use results::AnalysisReport;
+pub use results::CodeExample;
+pub use results::CustomVendor;
+pub use results::ImpactLevel;
+pub use results::KeywordMatch;
+pub use results::KeywordResult;
+pub use results::LabelMatch;
+pub use results::LabelResult;
+pub use results::Vendor;
@@ -1,32 +1,91 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::iter;
+use std::num::NonZeroUsize;
+use std::path::Path;
 use std::path::PathBuf;
+use std::process;
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 use std::time::Instant;
 
 use anyhow::Context;
 use anyhow::Result;
+use clap::ArgAction;
+use clap::CommandFactory;
 use clap::Parser;
+use clap::ValueEnum;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
 use tracing::level_filters::LevelFilter;
-use tracing_indicatif::IndicatifLayer;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::fmt;
 use tracing_subscriber::prelude::*;
 
-use analyzer::analyze_directory;
-
-mod analyzer;
-mod downloader;
-mod extractor;
-mod files;
-mod results;
+use keyword_impact_analyzer::analyzer::AnalysisOptions;
+use keyword_impact_analyzer::analyzer::DirectoryAnalysis;
+use keyword_impact_analyzer::analyzer::analyze_directory;
+use keyword_impact_analyzer::analyzer::analyze_directory_pipelined;
+use keyword_impact_analyzer::analyzer::analyze_directory_segregated;
+use keyword_impact_analyzer::analyzer::analyze_directory_streaming;
+use keyword_impact_analyzer::analyzer::analyze_string;
+use keyword_impact_analyzer::cache::ParseCache;
+use keyword_impact_analyzer::config::Config;
+use keyword_impact_analyzer::config::DEFAULT_CONFIG_FILE;
+use keyword_impact_analyzer::corpus_gen;
+use keyword_impact_analyzer::downloader;
+use keyword_impact_analyzer::downloader::DEFAULT_USER_AGENT;
+use keyword_impact_analyzer::downloader::HttpOptions;
+use keyword_impact_analyzer::downloader::PackageSelection;
+use keyword_impact_analyzer::downloader::PackagistConfig;
+use keyword_impact_analyzer::downloader::RetryPolicy;
+use keyword_impact_analyzer::downloader::VersionStrategy;
+use keyword_impact_analyzer::extractor;
+use keyword_impact_analyzer::files::PathFilter;
+use keyword_impact_analyzer::files::VendorFilter;
+use keyword_impact_analyzer::files::packages_incompatible_with;
+use keyword_impact_analyzer::files::php_extensions;
+use keyword_impact_analyzer::manifest::DownloadManifest;
+use keyword_impact_analyzer::match_store::MatchStore;
+use keyword_impact_analyzer::output::ReportOutput;
+use keyword_impact_analyzer::progress;
+use keyword_impact_analyzer::results::AnalysisReport;
+use keyword_impact_analyzer::results::CustomVendor;
+use keyword_impact_analyzer::results::ImpactLevel;
+use keyword_impact_analyzer::results::ImpactThresholds;
+use keyword_impact_analyzer::results::MIN_FILE_WARNING_THRESHOLD;
+use keyword_impact_analyzer::results::OutputFormat;
+use keyword_impact_analyzer::results::RenderOptions;
+use keyword_impact_analyzer::results::SortOrder;
+use keyword_impact_analyzer::results::Vendor;
+use keyword_impact_analyzer::timing::Timings;
 
 #[derive(Parser)]
 #[command(name = "keyword-impact-analyzer")]
 #[command(version = "0.1.0")]
 #[command(about = "Analyze keyword impact across PHP packages for RFC authors", long_about = None)]
+#[command(args_override_self = true)]
+#[command(after_help = "Example: keyword-impact-analyzer -k let --max 100")]
 struct Cli {
+    /// Read default settings from this TOML file [default: php-syntax-analyzer.toml, if present]
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
     /// Keywords to analyze (can be specified multiple times)
     #[arg(short, long, required = false)]
     keyword: Vec<String>,
 
+    /// File with one keyword per line (blank lines and `#` comments are ignored)
+    #[arg(long, value_name = "PATH")]
+    keywords_file: Option<PathBuf>,
+
     /// Labels to analyze ( goto label, and named arguments )
     #[arg(short, long, required = false)]
     label: Vec<String>,
@@ -35,25 +94,605 @@ struct Cli {
     #[arg(long, default_value_t = 0)]
     min: usize,
 
-    /// Maximum package index (0-based, exclusive)
-    #[arg(long, default_value_t = 500)]
-    max: usize,
+    /// Maximum package index (0-based, exclusive), i.e. an upper bound rather than a count;
+    /// required to download the most popular packages, e.g. -k let --max 100
+    #[arg(long)]
+    max: Option<usize>,
+
+    /// Number of packages to analyze starting at --min (sets --max to --min + N)
+    #[arg(long, value_name = "N", conflicts_with = "max")]
+    max_packages: Option<usize>,
+
+    /// File with one `vendor/package` name per line to download instead of the most
+    /// popular packages (blank lines and `#` comments are ignored); only these packages
+    /// are analyzed, also with --skip-download
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["min", "max", "max_packages", "top_packages_by_downloads"]
+    )]
+    packages_file: Option<PathBuf>,
+
+    /// `vendor/package` names to download instead of the most popular packages, comma-separated
+    /// or repeated; combined with --packages-file, and only these packages are analyzed
+    #[arg(
+        long = "packages",
+        value_name = "NAMES",
+        value_delimiter = ',',
+        action = ArgAction::Append,
+        conflicts_with_all = ["min", "max", "max_packages", "top_packages_by_downloads"]
+    )]
+    packages: Vec<String>,
 
     /// Download directory
     #[arg(short, long, default_value = "downloads")]
     directory: PathBuf,
 
-    /// Skip download phase (analyze existing sources only)
+    /// Directory for the parse cache and the download manifest [default: the download directory]
+    #[arg(long, value_name = "PATH")]
+    cache_dir: Option<PathBuf>,
+
+    /// Parse every file again instead of reusing cached matches
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Delete the parse cache before analyzing
+    #[arg(long)]
+    clear_cache: bool,
+
+    /// SQLite database storing every identifier of every analyzed file, so new keywords
+    /// can be checked without re-parsing unchanged files
+    #[arg(long, value_name = "PATH")]
+    match_cache_db: Option<PathBuf>,
+
+    /// Skip download phase (analyze existing sources only); with --packages or --packages-file,
+    /// the listed packages must already have been downloaded
     #[arg(long)]
     skip_download: bool,
+
+    /// Extra HTTP header to send with every request, as NAME=VALUE (can be specified multiple times)
+    #[arg(long = "http-header", value_name = "NAME=VALUE")]
+    http_headers: Vec<String>,
+
+    /// User-Agent sent with every request, e.g. to identify your organization's traffic
+    #[arg(long, value_name = "UA", default_value = DEFAULT_USER_AGENT)]
+    user_agent: String,
+
+    /// Base URL of a Packagist mirror to use instead of packagist.org (e.g. Private Packagist or Satis)
+    #[arg(long, value_name = "URL")]
+    packagist_url: Option<String>,
+
+    /// Base URL for package metadata, if it differs from --packagist-url [default: https://repo.packagist.org]
+    #[arg(long, value_name = "URL")]
+    packagist_metadata_url: Option<String>,
+
+    /// Only download packages of this Composer type, e.g. library, symfony-bundle,
+    /// laravel-package, wordpress-plugin or composer-plugin. Filtering is done by
+    /// Packagist, so an unknown type is not an error but yields fewer packages
+    #[arg(long, value_name = "TYPE")]
+    search_type: Option<String>,
+
+    /// Download the results of a Packagist full-text search (e.g. "orm") instead of the
+    /// most popular packages, still limited by --min and --max
+    #[arg(
+        long,
+        value_name = "TERM",
+        conflicts_with_all = ["packages_file", "packages"]
+    )]
+    search_query: Option<String>,
+
+    /// Only download packages whose `require.php` constraint allows this PHP version (e.g. 8.3)
+    #[arg(long, value_name = "VERSION")]
+    validate_php_version: Option<String>,
+
+    /// Only download packages with at least this many total downloads, according to their
+    /// Packagist statistics (cached in the download manifest); 0 downloads every package
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    min_downloads: u64,
+
+    /// Only analyze packages whose `composer.json` `require.php` constraint allows this PHP
+    /// version (e.g. 8.4); unlike --validate-php-version, this also applies to packages that
+    /// were downloaded before
+    #[arg(long, value_name = "VERSION")]
+    min_php_version: Option<String>,
+
+    /// Rank packages by total downloads instead of Packagist's popularity; this re-ranks
+    /// the first --max popular packages, so widely downloaded packages outside them are missed
+    #[arg(long)]
+    top_packages_by_downloads: bool,
+
+    /// Which version of each package to download
+    #[arg(long, value_enum, default_value_t = VersionStrategy::LatestStable)]
+    version_strategy: VersionStrategy,
+
+    /// Retry a download this many times on rate limiting, server or network errors
+    #[arg(long, default_value_t = 3)]
+    max_retries: usize,
+
+    /// Delay before the first retry, doubled after every attempt
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    retry_initial_delay_ms: u64,
+
+    /// Maximum number of requests per second sent to Packagist, across all downloads
+    #[arg(long, value_name = "RPS", default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    rate_limit: u32,
+
+    /// Maximum number of packages downloaded at the same time
+    #[arg(long, value_name = "N", default_value_t = NonZeroUsize::new(downloader::MAX_CONCURRENT_DOWNLOADS).unwrap())]
+    concurrency: NonZeroUsize,
+
+    /// Maximum number of package metadata requests in flight, before downloads start
+    #[arg(long, value_name = "N", default_value_t = NonZeroUsize::new(downloader::MAX_CONCURRENT_DOWNLOADS).unwrap())]
+    metadata_concurrency: NonZeroUsize,
+
+    /// Number of Packagist listing pages fetched at the same time
+    #[arg(long, value_name = "N", default_value_t = NonZeroUsize::new(3).unwrap())]
+    parallel_pages: NonZeroUsize,
+
+    /// Number of threads used to analyze files [default: number of CPUs]
+    #[arg(long, value_name = "N")]
+    analysis_threads: Option<NonZeroUsize>,
+
+    /// Number of threads used to extract archives, e.g. fewer on low-memory machines
+    /// [default: number of CPUs]
+    #[arg(long, value_name = "N")]
+    extract_threads: Option<NonZeroUsize>,
+
+    /// Extract and analyze packages as soon as they are downloaded, instead of after all
+    /// downloads, so that downloading and analysis overlap
+    #[arg(
+        long,
+        alias = "background-download",
+        conflicts_with_all = [
+            "skip_download",
+            "dry_run",
+            "stream",
+            "analyze_test_files_separately",
+            "min_php_version"
+        ]
+    )]
+    pipeline: bool,
+
+    /// Only print the packages (and versions) that would be downloaded, without downloading or analyzing anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Do not show progress bars (they are also hidden when stderr is not a terminal)
+    #[arg(long)]
+    no_progress: bool,
+
+    /// Do not color tables (also disabled by the NO_COLOR environment variable, or when
+    /// stdout is not a terminal)
+    #[arg(long)]
+    no_color: bool,
+
+    /// Format of the log messages written to stderr
+    #[arg(long, value_enum, default_value_t = LogFormat::Pretty)]
+    log_format: LogFormat,
+
+    /// Do not verify downloaded archives against Packagist's checksums (e.g. for private mirrors)
+    #[arg(long)]
+    skip_checksum: bool,
+
+    /// Delete the download manifest, so that every package is downloaded again
+    #[arg(long)]
+    reset_manifest: bool,
+
+    /// Order of the keywords in the table, Markdown, CSV and JSON reports
+    #[arg(
+        long = "sort-by",
+        visible_alias = "sort",
+        value_name = "FIELD",
+        value_enum,
+        default_value_t = SortOrder::HardImpact
+    )]
+    sort: SortOrder,
+
+    /// Show only the first N rows of the keyword table, after sorting (0 shows all)
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    top_n: usize,
+
+    /// Minimum number of matches for a low impact
+    #[arg(long, default_value_t = 1)]
+    threshold_low: usize,
+
+    /// Minimum number of matches for a medium impact
+    #[arg(long, default_value_t = 26)]
+    threshold_medium: usize,
+
+    /// Minimum number of matches for a high impact
+    #[arg(long, default_value_t = 101)]
+    threshold_high: usize,
+
+    /// Minimum number of matches for a critical impact
+    #[arg(long, default_value_t = 501)]
+    threshold_critical: usize,
+
+    /// Warn when fewer files are analyzed; 0 disables the warning
+    #[arg(long, default_value_t = MIN_FILE_WARNING_THRESHOLD)]
+    min_files_warning: usize,
+
+    /// Output format of the report
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Only report keywords whose hard impact is at least this level
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    min_severity: Option<ImpactLevel>,
+
+    /// Exit with a non-zero code (1 = low ... 4 = critical) if any keyword's hard impact reaches this level
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    fail_on: Option<ImpactLevel>,
+
+    /// Write the report to this file instead of stdout, without colors, and the packages
+    /// that failed to download, if any, with the reason, to `<name>-failures.json` next to it
+    #[arg(long, value_name = "PATH")]
+    output_file: Option<PathBuf>,
+
+    /// Run this shell command once the report is written, with the report path and the
+    /// exit code of the analysis as its arguments, e.g. to upload the report
+    #[arg(long, value_name = "COMMAND", requires = "output_file")]
+    post_hook: Option<String>,
+
+    /// Show which packages drive each keyword's counts, and where every match is
+    #[arg(long)]
+    display: bool,
+
+    /// Print the paths of the files with matches, one per line, instead of the report
+    #[arg(long, conflicts_with_all = ["load_report", "analyze_test_files_separately"])]
+    files_with_matches: bool,
+
+    /// Separate the paths of --files-with-matches with NUL bytes instead of newlines, like
+    /// `find -print0`, for `xargs -0`
+    #[arg(short = '0', long, requires = "files_with_matches")]
+    print0: bool,
+
+    /// Save the analysis report to a file, to be displayed later with --load-report
+    #[arg(long, value_name = "PATH")]
+    save_report: Option<PathBuf>,
+
+    /// Merge a report saved with --save-report (e.g. from another package range) into the results
+    #[arg(long, value_name = "PATH")]
+    merge: Option<PathBuf>,
+
+    /// Only analyze packages missing from a report saved with --save-report, and merge
+    /// that report into the results (e.g. after increasing --max)
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["merge", "load_report", "analyze_test_files_separately"]
+    )]
+    baseline_report: Option<PathBuf>,
+
+    /// Show how the results changed since a report saved with --save-report; only with
+    /// --format table
+    #[arg(long, value_name = "PATH")]
+    compare: Option<PathBuf>,
+
+    /// Display a report saved with --save-report instead of running an analysis
+    #[arg(long, value_name = "PATH", conflicts_with = "save_report")]
+    load_report: Option<PathBuf>,
+
+    /// Print a focused table of a single well-known or custom vendor's exposure (e.g. symfony);
+    /// only with --format table
+    #[arg(long, value_name = "VENDOR")]
+    vendor_report: Option<String>,
+
+    /// Print the packages using this keyword, grouped by the impact of their own matches; only
+    /// with --format table
+    #[arg(long, value_name = "KEYWORD")]
+    drill_down: Option<String>,
+
+    /// Print a table of the N packages with the most matches of each keyword; only with
+    /// --format table
+    #[arg(long, value_name = "N")]
+    show_top_packages: Option<usize>,
+
+    /// Report production and test files (detected by path) in separate tables; the report
+    /// cannot be saved, merged or compared, and the extra reports are not available
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "save_report",
+            "merge",
+            "compare",
+            "summary",
+            "timing",
+            "drill_down",
+            "show_top_packages",
+            "vendor_report",
+            "correlation_report",
+            "show_cooccurrence",
+            "show_histogram",
+            "vendor_breakdown"
+        ]
+    )]
+    analyze_test_files_separately: bool,
+
+    /// Print each keyword match as `file:line: keyword: kind (hard|soft)` as soon as its file
+    /// is analyzed, instead of building the report, so that memory use does not grow with
+    /// the number of matches
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "label",
+            "analyze_test_files_separately",
+            "files_with_matches",
+            "load_report",
+            "save_report",
+            "merge",
+            "baseline_report",
+            "compare",
+            "format",
+            "display",
+            "sort",
+            "top_n",
+            "min_severity",
+            "fail_on",
+            "summary",
+            "timing",
+            "drill_down",
+            "show_top_packages",
+            "vendor_report",
+            "correlation_report",
+            "show_cooccurrence",
+            "show_histogram",
+            "vendor_breakdown"
+        ]
+    )]
+    stream: bool,
+
+    /// Also report string array keys matching a keyword (e.g. `['let' => 1]`); noisy
+    #[arg(long)]
+    analyze_string_keys: bool,
+
+    /// Also report variables named after a keyword (e.g. `$scope`); expect very high counts
+    /// for common words
+    #[arg(long)]
+    include_variables: bool,
+
+    /// Only match names spelled exactly like a keyword (e.g. `let` but not `Let`). PHP
+    /// identifiers are case-insensitive, so this undercounts what reserving a keyword breaks
+    #[arg(long)]
+    case_sensitive: bool,
+
+    /// Count each keyword at most once as a soft and once as a hard match per file, so
+    /// that counts are of affected files rather than occurrences
+    #[arg(long)]
+    dedup_per_file: bool,
+
+    /// Only analyze packages of this vendor, e.g. `symfony` (can be specified multiple times)
+    #[arg(long = "vendor", value_name = "NAME", action = ArgAction::Append)]
+    vendors: Vec<String>,
+
+    /// Skip packages of this vendor, e.g. `phpunit` (can be specified multiple times)
+    #[arg(long = "exclude-vendor", value_name = "NAME", action = ArgAction::Append)]
+    excluded_vendors: Vec<String>,
+
+    /// Treat packages of this in-house vendor as well-known, e.g. `acme`, or `acme=Acme Corp`
+    /// to choose its display name (can be specified multiple times)
+    #[arg(
+        long = "custom-vendor",
+        value_name = "PREFIX",
+        action = ArgAction::Append,
+        value_parser = parse_custom_vendor
+    )]
+    custom_vendors: Vec<CustomVendor>,
+
+    /// Only analyze files whose path below the sources directory matches this glob,
+    /// e.g. `*/src/**/*.php` (can be specified multiple times)
+    #[arg(long = "include-pattern", value_name = "GLOB", action = ArgAction::Append)]
+    include_patterns: Vec<String>,
+
+    /// Skip files and directories whose path below the sources directory matches this
+    /// glob, e.g. `*/tests/*` (can be specified multiple times)
+    #[arg(long = "exclude-pattern", value_name = "GLOB", action = ArgAction::Append)]
+    exclude_patterns: Vec<String>,
+
+    /// Also analyze `.phtml` templates, common in Laminas and Zend Framework projects
+    #[arg(long)]
+    include_phtml: bool,
+
+    /// Also analyze `.inc` files, common in legacy code bases
+    #[arg(long)]
+    include_inc: bool,
+
+    /// Only analyze packages from well-known vendors
+    #[arg(long)]
+    skip_other_vendors: bool,
+
+    /// Print a vendor × keyword table showing hard (H) and soft (S) usage; only with
+    /// --format table
+    #[arg(long)]
+    correlation_report: bool,
+
+    /// Print a keyword × keyword table of how many files use both keywords; only with
+    /// --format table
+    #[arg(long)]
+    show_cooccurrence: bool,
+
+    /// Print a bar chart per keyword of how many packages use it 1, 2-5, 6-25, 26-100,
+    /// 101-500 and 501+ times; only with --format table
+    #[arg(long)]
+    show_histogram: bool,
+
+    /// Print each keyword's soft and hard counts per vendor; only with --format table
+    #[arg(long)]
+    vendor_breakdown: bool,
+
+    /// Print a `RESULT keyword=... soft=... hard=...` line per keyword after the table, for
+    /// shell scripts; only with --format table
+    #[arg(long)]
+    summary: bool,
+
+    /// Print the slowest packages to download and analyze, and the mean, median
+    /// and 99th percentile duration of each phase; only with --format table
+    #[arg(long)]
+    timing: bool,
+
+    /// Analyze PHP source read from stdin instead of downloaded packages, printing one
+    /// match per line (to --output-file if given) instead of the report
+    #[arg(long, conflicts_with = "format")]
+    stdin: bool,
+
+    /// Generate a synthetic PHP corpus for benchmarking in the given directory
+    #[arg(long, value_name = "DIR")]
+    generate_corpus: Option<PathBuf>,
+
+    /// Number of files to generate with --generate-corpus
+    #[arg(long, default_value_t = 1000)]
+    corpus_size: usize,
+
+    /// Probability (0.0-1.0) that a generated identifier matches one of the keywords
+    #[arg(long, default_value_t = 0.01)]
+    corpus_keyword_density: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line, for log aggregation systems
+    Json,
+}
+
+/// Reads one entry per line, e.g. of a keywords or packages file.
+fn read_list_file(path: &Path, kind: &str) -> Result<Vec<String>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {} file {:?}", kind, path))?;
+
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+fn parse_vendor(name: &str, custom_vendors: &[CustomVendor]) -> Result<Vendor> {
+    match Vendor::from_package(&format!("{}/", name.to_lowercase()), custom_vendors) {
+        Vendor::Other => anyhow::bail!("{:?} is not a well-known or custom vendor", name),
+        vendor => Ok(vendor),
+    }
+}
+
+/// Parses `prefix` or `prefix=display name`; the display name defaults to the prefix.
+fn parse_custom_vendor(value: &str) -> Result<CustomVendor, String> {
+    let (prefix, name) = value.split_once('=').unwrap_or((value, value));
+    let prefix = prefix.trim().trim_end_matches('/').to_lowercase();
+    if prefix.is_empty() || prefix.contains('/') {
+        return Err(format!("{:?} is not a vendor name", value));
+    }
+
+    let name = match name.trim().trim_end_matches('/') {
+        "" => prefix.as_str(),
+        name => name,
+    };
+
+    Ok((format!("{}/", prefix).into(), name.into()))
+}
+
+/// Runs the `--post-hook` on the written report, if any, and exits with the code of
+/// `max_impact` if it reaches `fail_on`.
+fn finish(
+    max_impact: ImpactLevel,
+    fail_on: Option<ImpactLevel>,
+    post_hook: Option<(&str, &Path)>,
+) -> Result<()> {
+    let exit_code = match fail_on {
+        Some(fail_on) if max_impact >= fail_on && max_impact.exit_code() != 0 => {
+            tracing::warn!(
+                "Maximum impact {} reached --fail-on {}",
+                max_impact.as_str(),
+                fail_on.as_str()
+            );
+            max_impact.exit_code()
+        }
+        _ => 0,
+    };
+
+    if let Some((command, report_path)) = post_hook {
+        run_post_hook(command, report_path, exit_code)?;
+    }
+
+    if exit_code != 0 {
+        process::exit(exit_code);
+    }
+
+    Ok(())
+}
+
+/// Runs `command` through the shell with the report path and the exit code of the
+/// analysis as its arguments. A failing hook is reported, but does not change the
+/// exit code.
+fn run_post_hook(command: &str, report_path: &Path, exit_code: i32) -> Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    } else {
+        // `"$@"` hands the arguments following the script name on to the command.
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(format!("{} \"$@\"", command)).arg("sh");
+        shell
+    };
+
+    tracing::info!("Running post hook {:?}", command);
+    let status = shell
+        .arg(report_path)
+        .arg(exit_code.to_string())
+        .status()
+        .with_context(|| format!("Failed to run post hook {:?}", command))?;
+
+    if !status.success() {
+        tracing::warn!("Post hook {:?} failed: {}", command, status);
+    }
+
+    Ok(())
+}
+
+/// Returns where the download failures of a run writing its report to `output_file` are
+/// saved, e.g. `report-failures.json` next to `report.md`.
+fn failures_path(output_file: &Path) -> PathBuf {
+    let stem = output_file
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy();
+
+    output_file.with_file_name(format!("{}-failures.json", stem))
+}
+
+/// Parses the command line, using the settings of the config file as defaults.
+fn parse_cli() -> Result<Cli> {
+    let cli = Cli::parse();
+    let path = match cli.config {
+        Some(path) => path,
+        None if Path::new(DEFAULT_CONFIG_FILE).exists() => PathBuf::from(DEFAULT_CONFIG_FILE),
+        None => return Ok(cli),
+    };
+
+    let config_args = Config::load(&path)?.to_args(&Cli::command())?;
+
+    // Arguments given later override earlier ones, so the actual command line wins.
+    let mut args = env::args_os();
+    let program = args.next().unwrap_or_default();
+    let args = iter::once(program)
+        .chain(config_args.into_iter().map(OsString::from))
+        .chain(args);
+
+    Ok(Cli::parse_from(args))
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let indicatif_layer = IndicatifLayer::new();
+    let mut cli = parse_cli()?;
+
+    let timings = cli.timing.then(Timings::default);
 
     tracing_subscriber::registry()
-        .with(indicatif_layer)
+        .with(progress::layer(cli.no_progress))
+        .with(timings.as_ref().map(Timings::layer))
         .with(
             EnvFilter::from_env("RUST_LOG")
                 .add_directive(LevelFilter::INFO.into())
@@ -83,46 +722,471 @@ async fn main() -> Result<()> {
                         .expect("Failed to parse RUST_LOG directive"),
                 ),
         )
-        .with(
+        .with((cli.log_format == LogFormat::Pretty).then(|| {
             fmt::layer()
+                .with_writer(io::stderr)
                 .without_time()
                 .with_target(false)
                 .with_thread_ids(false)
-                .with_level(true),
+                .with_level(true)
+        }))
+        .with(
+            (cli.log_format == LogFormat::Json)
+                .then(|| fmt::layer().json().with_writer(io::stderr)),
         )
         .init();
 
-    let cli = Cli::parse();
+    if let Some(keywords_file) = &cli.keywords_file {
+        for keyword in read_list_file(keywords_file, "keywords")? {
+            if !cli.keyword.contains(&keyword) {
+                cli.keyword.push(keyword);
+            }
+        }
+    }
+
+    let options = AnalysisOptions {
+        analyze_string_keys: cli.analyze_string_keys,
+        include_variables: cli.include_variables,
+        skip_other_vendors: cli.skip_other_vendors,
+        case_sensitive: cli.case_sensitive,
+        dedup_per_file: cli.dedup_per_file,
+        keep_locations: cli.display
+            || cli.show_cooccurrence
+            || cli.files_with_matches
+            || cli.format == OutputFormat::Html,
+    };
+
+    let vendor_report = cli
+        .vendor_report
+        .as_deref()
+        .map(|name| parse_vendor(name, &cli.custom_vendors))
+        .transpose()?;
+
+    if let Some(corpus_dir) = &cli.generate_corpus {
+        if !(0.0..=1.0).contains(&cli.corpus_keyword_density) {
+            anyhow::bail!("Corpus keyword density must be between 0.0 and 1.0");
+        }
+
+        corpus_gen::generate_corpus(
+            corpus_dir,
+            cli.corpus_size,
+            &cli.keyword,
+            cli.corpus_keyword_density,
+        )
+        .context("Failed to generate corpus")?;
+
+        return Ok(());
+    }
+
+    let thresholds = ImpactThresholds::new(
+        cli.threshold_low,
+        cli.threshold_medium,
+        cli.threshold_high,
+        cli.threshold_critical,
+    )?;
+
+    let render_options = RenderOptions {
+        format: cli.format,
+        show_keywords: !cli.keyword.is_empty(),
+        show_labels: !cli.label.is_empty(),
+        sort: cli.sort,
+        top_n: cli.top_n,
+        display: cli.display,
+    };
+
+    // The extra reports are tables, which would break machine-readable output.
+    if cli.format != OutputFormat::Table {
+        let table_only_flags = [
+            ("--compare", cli.compare.is_some()),
+            ("--vendor-report", cli.vendor_report.is_some()),
+            ("--drill-down", cli.drill_down.is_some()),
+            ("--show-top-packages", cli.show_top_packages.is_some()),
+            ("--correlation-report", cli.correlation_report),
+            ("--show-cooccurrence", cli.show_cooccurrence),
+            ("--show-histogram", cli.show_histogram),
+            ("--vendor-breakdown", cli.vendor_breakdown),
+            ("--summary", cli.summary),
+            ("--timing", cli.timing),
+        ];
+        if let Some((flag, _)) = table_only_flags.iter().find(|(_, given)| *given) {
+            anyhow::bail!("{} is only available with --format table", flag);
+        }
+    }
+
+    let mut out = match &cli.output_file {
+        Some(path) => ReportOutput::file(path)?,
+        None => ReportOutput::stdout(cli.no_color),
+    };
+    let post_hook = cli.post_hook.as_deref().zip(cli.output_file.as_deref());
+
+    if let Some(report_path) = &cli.load_report {
+        let mut report = AnalysisReport::load(report_path)?;
+        report.thresholds = thresholds;
+        report.set_min_file_warning_threshold(cli.min_files_warning);
+        let max_impact = report.max_impact();
+        if let Some(min) = cli.min_severity {
+            report.filter_by_severity(min);
+        }
+
+        report.render(
+            &mut out,
+            &RenderOptions {
+                show_keywords: !report.keyword_results.is_empty(),
+                show_labels: !report.label_results.is_empty(),
+                ..render_options
+            },
+        )?;
+        out.flush()?;
+        finish(max_impact, cli.fail_on, post_hook)?;
 
-    if cli.keyword.is_empty() && cli.label.is_empty() {
+        return Ok(());
+    }
+
+    if !cli.dry_run && cli.keyword.is_empty() && cli.label.is_empty() {
         anyhow::bail!("At least one keyword or label must be specified for analysis");
     }
 
-    if cli.min >= cli.max {
-        anyhow::bail!("Minimum index must be less than maximum index");
+    if cli.stdin {
+        let mut source = String::new();
+        io::stdin()
+            .read_to_string(&mut source)
+            .context("Failed to read PHP source from stdin")?;
+
+        let matches = analyze_string(source, &cli.keyword, &cli.label, options);
+        if matches.has_parse_error {
+            tracing::warn!("The source has parse errors, so some matches may be missing");
+        }
+
+        for m in matches.keyword_matches {
+            writeln!(
+                out,
+                "{}: {} ({}) at offset {}",
+                m.keyword,
+                m.kind.as_str(),
+                if m.is_hard { "hard" } else { "soft" },
+                m.offset
+            )?;
+        }
+
+        for m in matches.label_matches {
+            writeln!(out, "{}: label", m.label)?;
+        }
+        out.flush()?;
+        finish(ImpactLevel::None, None, post_hook)?;
+
+        return Ok(());
+    }
+
+    let selection = if cli.packages_file.is_some() || !cli.packages.is_empty() {
+        let mut packages = cli.packages.clone();
+        if let Some(packages_file) = &cli.packages_file {
+            packages.extend(read_list_file(packages_file, "packages")?);
+        }
+
+        let mut packages: Vec<String> = packages
+            .iter()
+            .map(|package| package.trim().to_lowercase())
+            .filter(|package| !package.is_empty())
+            .collect();
+        packages.sort();
+        packages.dedup();
+
+        PackageSelection::Names(packages)
+    } else {
+        let max = match (cli.max_packages, cli.max) {
+            (Some(count), _) => cli.min + count,
+            (None, Some(max)) => max,
+            // Nothing is downloaded, so the range is never used.
+            (None, None) if cli.skip_download && !cli.dry_run => usize::MAX,
+            (None, None) => anyhow::bail!(
+                "Choose how many packages to download with --max or --max-packages (e.g. -k let --max 100), or list them with --packages or --packages-file"
+            ),
+        };
+
+        if cli.min >= max {
+            anyhow::bail!("Minimum index must be less than maximum index");
+        }
+
+        PackageSelection::Top {
+            min: cli.min,
+            max,
+            by_downloads: cli.top_packages_by_downloads,
+        }
+    };
+
+    if cli.analyze_test_files_separately && cli.format == OutputFormat::Csv {
+        anyhow::bail!("--format csv cannot be combined with --analyze-test-files-separately");
+    }
+
+    if cli.analyze_test_files_separately && cli.format == OutputFormat::Html {
+        anyhow::bail!("--format html cannot be combined with --analyze-test-files-separately");
+    }
+
+    // Checked before downloading, so that a typo does not surface only after it.
+    let mut path_filter = PathFilter::new(
+        php_extensions(cli.include_phtml, cli.include_inc),
+        &cli.include_patterns,
+        &cli.exclude_patterns,
+    )?;
+    if let PackageSelection::Names(packages) = &selection {
+        path_filter = path_filter.only_packages(packages);
+
+        // Without downloading, a listed package that was never downloaded would
+        // silently be missing from the results.
+        if cli.skip_download {
+            let sources_dir = cli.directory.join("sources");
+            let missing: Vec<&str> = packages
+                .iter()
+                .filter(|package| !sources_dir.join(package).is_dir())
+                .map(String::as_str)
+                .collect();
+
+            if !missing.is_empty() && missing.len() == packages.len() {
+                anyhow::bail!(
+                    "None of the listed packages is in {:?}; run without --skip-download to download them",
+                    sources_dir
+                );
+            }
+
+            if !missing.is_empty() {
+                tracing::warn!(
+                    "{} listed packages are not in {:?} and will not be analyzed: {}",
+                    missing.len(),
+                    sources_dir,
+                    missing.join(", ")
+                );
+            }
+        }
+    }
+
+    let baseline = match &cli.baseline_report {
+        Some(baseline_path) => {
+            let baseline = AnalysisReport::load(baseline_path)?;
+
+            // Skipped packages are only counted for the keywords the baseline has results for.
+            let keywords: BTreeSet<&String> = cli.keyword.iter().collect();
+            if baseline.keyword_results.keys().collect::<BTreeSet<_>>() != keywords {
+                anyhow::bail!(
+                    "Baseline report {:?} was created for different keywords",
+                    baseline_path
+                );
+            }
+
+            if baseline.total_files > 0 && baseline.analyzed_packages.is_empty() {
+                anyhow::bail!(
+                    "Baseline report {:?} lacks a package list; save it again with --save-report",
+                    baseline_path
+                );
+            }
+
+            path_filter = path_filter.skip_packages(&baseline.analyzed_packages);
+
+            Some(baseline)
+        }
+        None => None,
+    };
+
+    let packagist = PackagistConfig::new(
+        cli.packagist_url.as_deref(),
+        cli.packagist_metadata_url.as_deref(),
+        cli.search_type.as_deref(),
+        cli.search_query.as_deref(),
+    );
+    let http = HttpOptions {
+        headers: &cli.http_headers,
+        user_agent: &cli.user_agent,
+        packagist: &packagist,
+        retry: RetryPolicy {
+            max_retries: cli.max_retries,
+            initial_delay: Duration::from_millis(cli.retry_initial_delay_ms),
+        },
+        requests_per_second: cli.rate_limit,
+        verify_checksums: !cli.skip_checksum,
+        concurrency: cli.concurrency.get(),
+        metadata_concurrency: cli.metadata_concurrency.get(),
+        parallel_pages: cli.parallel_pages.get(),
+        version_strategy: cli.version_strategy,
+    };
+
+    if cli.dry_run {
+        let planned = downloader::plan_downloads(
+            &selection,
+            cli.validate_php_version.as_deref(),
+            cli.min_downloads,
+            http,
+        )
+        .await
+        .context("Failed to resolve packages")?;
+
+        if cli.format == OutputFormat::Json {
+            writeln!(out, "{}", serde_json::to_string_pretty(&planned)?)?;
+        } else {
+            for package in &planned {
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    package.package, package.version, package.url
+                )?;
+            }
+        }
+        out.flush()?;
+
+        return Ok(());
     }
 
     let start_time = Instant::now();
 
+    // Holds the parse cache and the download manifest.
+    let cache_root = cli.cache_dir.as_ref().unwrap_or(&cli.directory);
+    let cache_dir = cache_root.join(".php-syntax-analyzer-cache");
+
+    if cli.clear_cache {
+        ParseCache::clear(&cache_dir)?;
+        tracing::info!("Cleared parse cache {:?}", cache_dir);
+    }
+
+    let cache_dir = (!cli.no_cache).then_some(cache_dir);
+
+    let match_store = cli
+        .match_cache_db
+        .as_deref()
+        .map(MatchStore::open)
+        .transpose()?;
+
+    let extraction_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(cli.extract_threads.map_or(0, NonZeroUsize::get))
+        .thread_name(|index| format!("extraction-{}", index))
+        .build()
+        .context("Failed to create extraction thread pool")?;
+
+    // Only the analysis runs in this pool, so it can be tuned apart from extraction.
+    let analysis_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(cli.analysis_threads.map_or(0, NonZeroUsize::get))
+        .thread_name(|index| format!("analysis-{}", index))
+        .build()
+        .context("Failed to create analysis thread pool")?;
+
+    if cli.reset_manifest {
+        DownloadManifest::reset(cache_root)?;
+    }
+
+    let mut manifest = DownloadManifest::load(cache_root)?;
+    let mut downloaded = Vec::new();
+    let mut pipelined_report = None;
     if !cli.skip_download {
-        tracing::info!(
-            "Downloading packages {} to {} to {:?}",
-            cli.min,
-            cli.max,
-            cli.directory
-        );
+        match &selection {
+            PackageSelection::Top { min, max, .. } => tracing::info!(
+                "Downloading packages {} to {} to {:?}",
+                min,
+                max,
+                cli.directory
+            ),
+            PackageSelection::Names(packages) => tracing::info!(
+                "Downloading {} listed packages to {:?}",
+                packages.len(),
+                cli.directory
+            ),
+        }
 
         let download_start = Instant::now();
-        let (successful, failed) =
-            downloader::download_packages(cli.directory.clone(), cli.min, cli.max)
-                .await
-                .context("Failed to download packages")?;
+        let (entries, failures) = if cli.pipeline {
+            // Archives left by earlier runs are extracted up front, the others as they arrive.
+            extraction_pool
+                .install(|| extractor::extract_packages(cli.directory.clone()))
+                .context("Failed to extract packages")?;
 
-        if failed > 0 {
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+            let batches = iter::from_fn(|| {
+                // Packages downloaded while the previous batch was analyzed form the next one.
+                let mut batch = vec![receiver.blocking_recv()?];
+                while let Ok(package) = receiver.try_recv() {
+                    batch.push(package);
+                }
+
+                Some(
+                    extraction_pool
+                        .install(|| extractor::extract_listed_packages(&cli.directory, &batch)),
+                )
+            });
+
+            // The downloads keep running on this runtime while a scoped thread analyzes
+            // each batch in the analysis pool, so that both can borrow the settings.
+            let (downloads, report) = tokio::task::block_in_place(|| {
+                thread::scope(|scope| {
+                    let analysis = scope.spawn(|| {
+                        analysis_pool.install(|| {
+                            let directory_analysis = DirectoryAnalysis {
+                                sources_directory: cli.directory.join("sources"),
+                                cache_directory: cache_dir.clone(),
+                                keywords: cli.keyword.clone(),
+                                labels: cli.label.clone(),
+                                vendor_filter: VendorFilter::new(
+                                    &cli.vendors,
+                                    &cli.excluded_vendors,
+                                ),
+                                custom_vendors: &cli.custom_vendors,
+                                path_filter: path_filter.clone(),
+                                options,
+                                match_store: match_store.as_ref(),
+                            };
+
+                            analyze_directory_pipelined(directory_analysis, batches)
+                        })
+                    });
+
+                    let downloads = Handle::current().block_on(downloader::download_packages(
+                        cli.directory.clone(),
+                        &selection,
+                        cli.validate_php_version.as_deref(),
+                        cli.min_downloads,
+                        http,
+                        &mut manifest,
+                        Some(&sender),
+                    ));
+                    // Ends the batches, even if the downloads failed.
+                    drop(sender);
+
+                    (
+                        downloads,
+                        analysis.join().expect("Pipelined analysis panicked"),
+                    )
+                })
+            });
+            pipelined_report = Some(report.context("Failed to analyze directory")?);
+
+            downloads
+        } else {
+            downloader::download_packages(
+                cli.directory.clone(),
+                &selection,
+                cli.validate_php_version.as_deref(),
+                cli.min_downloads,
+                http,
+                &mut manifest,
+                None,
+            )
+            .await
+        }
+        .context("Failed to download packages")?;
+        downloaded = entries;
+        let successful = downloaded.len();
+
+        for failure in &failures {
+            tracing::warn!("Failed to download {}: {}", failure.package, failure.reason);
+        }
+
+        if let Some(output_file) = cli.output_file.as_deref().filter(|_| !failures.is_empty()) {
+            let failures_path = failures_path(output_file);
+            fs::write(&failures_path, serde_json::to_string_pretty(&failures)?).with_context(
+                || format!("Failed to write download failures to {:?}", failures_path),
+            )?;
+        }
+
+        if !failures.is_empty() {
             tracing::warn!(
                 "Download complete: {} successful, {} failed",
                 successful,
-                failed
+                failures.len()
             );
         } else {
             tracing::info!("All {} packages downloaded successfully", successful);
@@ -139,8 +1203,16 @@ async fn main() -> Result<()> {
     }
 
     let extract_start = Instant::now();
-    let extracted =
-        extractor::extract_packages(cli.directory.clone()).context("Failed to extract packages")?;
+    let extracted = extraction_pool
+        .install(|| extractor::extract_packages(cli.directory.clone()))
+        .context("Failed to extract packages")?;
+
+    // Only packages that made it all the way to their sources are resumable.
+    manifest.record_all(
+        downloaded
+            .into_iter()
+            .filter(|entry| cli.directory.join("sources").join(&entry.package).exists()),
+    )?;
 
     let extract_duration = extract_start.elapsed();
     tracing::info!(
@@ -159,22 +1231,208 @@ async fn main() -> Result<()> {
         );
     }
 
-    let has_keywords = !cli.keyword.is_empty();
-    let has_labels = !cli.label.is_empty();
+    if let Some(php_version) = &cli.min_php_version {
+        let incompatible = packages_incompatible_with(&sources_dir, php_version)?;
+        tracing::info!(
+            "Skipping {} packages not compatible with PHP {} according to their composer.json",
+            incompatible.len(),
+            php_version
+        );
 
-    let report = analyze_directory(sources_dir, cli.keyword, cli.label)
-        .context("Failed to analyze directory")?;
+        path_filter = path_filter.skip_packages(&incompatible);
+    }
 
-    let analysis_duration = analysis_start.elapsed();
-    tracing::info!(
-        "Analysis completed in {:.2}s",
-        analysis_duration.as_secs_f64()
-    );
+    let analysis = DirectoryAnalysis {
+        sources_directory: sources_dir,
+        cache_directory: cache_dir,
+        keywords: cli.keyword,
+        labels: cli.label,
+        vendor_filter: VendorFilter::new(&cli.vendors, &cli.excluded_vendors),
+        custom_vendors: &cli.custom_vendors,
+        path_filter,
+        options,
+        match_store: match_store.as_ref(),
+    };
+
+    if cli.analyze_test_files_separately {
+        let mut report = analysis_pool
+            .install(|| analyze_directory_segregated(analysis))
+            .context("Failed to analyze directory")?;
+        report.production_results.thresholds = thresholds;
+        report.test_results.thresholds = thresholds;
+        report
+            .production_results
+            .set_min_file_warning_threshold(cli.min_files_warning);
+        report
+            .test_results
+            .set_min_file_warning_threshold(cli.min_files_warning);
+
+        tracing::info!(
+            "Analysis completed in {:.2}s",
+            analysis_start.elapsed().as_secs_f64()
+        );
+
+        let max_impact = report
+            .production_results
+            .max_impact()
+            .max(report.test_results.max_impact());
+        if let Some(min) = cli.min_severity {
+            report.production_results.filter_by_severity(min);
+            report.test_results.filter_by_severity(min);
+        }
+
+        report.render(&mut out, &render_options)?;
+        out.flush()?;
+        finish(max_impact, cli.fail_on, post_hook)?;
+
+        return Ok(());
+    }
+
+    if cli.stream {
+        // Matches are written from the collector thread; the first write error stops
+        // further output and is reported once the analysis is done.
+        let stream_out = Mutex::new((&mut out, Ok(())));
+        let total_files = analysis_pool
+            .install(|| {
+                analyze_directory_streaming(analysis, |matches| {
+                    let (out, result) = &mut *stream_out.lock().unwrap();
+                    if result.is_ok() {
+                        *result = matches.iter().try_for_each(|m| {
+                            writeln!(
+                                out,
+                                "{}:{}: {}: {} ({})",
+                                m.file,
+                                m.line,
+                                m.keyword,
+                                m.kind.as_str(),
+                                if m.is_hard { "hard" } else { "soft" }
+                            )
+                        });
+                    }
+                })
+            })
+            .context("Failed to analyze directory")?;
+        let (out, result) = stream_out.into_inner().unwrap();
+        result.context("Failed to write matches")?;
+        out.flush()?;
+
+        tracing::info!(
+            "Analyzed {} files in {:.2}s",
+            total_files,
+            analysis_start.elapsed().as_secs_f64()
+        );
+        finish(ImpactLevel::None, None, post_hook)?;
+
+        return Ok(());
+    }
+
+    let mut report = match pipelined_report {
+        // Analyzed while downloading, which the download time above already includes.
+        Some(report) => report,
+        None => {
+            let report = analysis_pool
+                .install(|| analyze_directory(analysis))
+                .context("Failed to analyze directory")?;
+
+            let analysis_duration = analysis_start.elapsed();
+            tracing::info!(
+                "Analysis completed in {:.2}s",
+                analysis_duration.as_secs_f64()
+            );
+
+            report
+        }
+    };
+    report.thresholds = thresholds;
+    report.set_min_file_warning_threshold(cli.min_files_warning);
 
     let total_duration = start_time.elapsed();
     tracing::info!("Total time: {:.2}s", total_duration.as_secs_f64());
 
-    report.display_table(has_keywords, has_labels);
+    if let Some(baseline) = baseline {
+        tracing::info!(
+            "Reused {} packages from baseline report",
+            baseline.analyzed_packages.len()
+        );
+        report.merge(baseline);
+    }
+
+    if let Some(report_path) = &cli.merge {
+        report.merge(AnalysisReport::load(report_path)?);
+        tracing::info!("Merged report from {:?}", report_path);
+    }
+
+    if let Some(report_path) = &cli.save_report {
+        report.save(report_path)?;
+        tracing::info!("Saved report to {:?}", report_path);
+    }
+
+    // Filter only after saving, so the saved report keeps every keyword.
+    let max_impact = report.max_impact();
+    if let Some(min) = cli.min_severity {
+        report.filter_by_severity(min);
+    }
+
+    if cli.files_with_matches {
+        let separator = if cli.print0 { '\0' } else { '\n' };
+        for file in report.matched_files() {
+            write!(out, "{}{}", file, separator)?;
+        }
+        out.flush()?;
+        finish(max_impact, cli.fail_on, post_hook)?;
+
+        return Ok(());
+    }
+
+    report.render(&mut out, &render_options)?;
+
+    if report.is_critical() {
+        tracing::warn!("At least one keyword has critical impact");
+    }
+
+    if cli.summary {
+        report.display_summary(&mut out, cli.sort)?;
+    }
+
+    if let Some(keyword) = &cli.drill_down {
+        report.display_drill_down(&mut out, keyword)?;
+    }
+
+    if let Some(n) = cli.show_top_packages {
+        report.display_top_packages(&mut out, n, cli.sort)?;
+    }
+
+    if let Some(vendor) = &vendor_report {
+        report.display_vendor_report(&mut out, vendor)?;
+    }
+
+    if let Some(baseline_path) = &cli.compare {
+        let baseline = AnalysisReport::load(baseline_path)?;
+        report.diff(&baseline).display_table(&mut out)?;
+    }
+
+    if cli.correlation_report {
+        report.display_correlation_report(&mut out)?;
+    }
+
+    if cli.show_cooccurrence {
+        report.display_cooccurrence_report(&mut out)?;
+    }
+
+    if cli.show_histogram {
+        report.display_histograms(&mut out, cli.sort)?;
+    }
+
+    if cli.vendor_breakdown {
+        report.display_vendor_breakdown(&mut out, cli.sort)?;
+    }
+
+    if let Some(timings) = &timings {
+        timings.display(&mut out)?;
+    }
+
+    out.flush()?;
+    finish(max_impact, cli.fail_on, post_hook)?;
 
     Ok(())
 }
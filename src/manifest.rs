@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub package: String,
+    pub version: String,
+    pub sha1: String,
+    /// Seconds since the Unix epoch.
+    pub downloaded_at: u64,
+}
+
+/// The manifest was a bare list of entries before download statistics were cached.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StoredManifest {
+    Current {
+        packages: Vec<ManifestEntry>,
+        #[serde(default)]
+        total_downloads: BTreeMap<String, u64>,
+    },
+    Legacy(Vec<ManifestEntry>),
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestFile<'a> {
+    packages: Vec<&'a ManifestEntry>,
+    total_downloads: &'a BTreeMap<String, u64>,
+}
+
+/// Packages that were both downloaded and extracted, so that an interrupted run
+/// can be resumed without trusting half-written zipballs or source directories.
+///
+/// It also keeps the total downloads fetched for `--min-downloads`, so they are not
+/// fetched again by later runs.
+#[derive(Debug)]
+pub struct DownloadManifest {
+    path: PathBuf,
+    entries: BTreeMap<String, ManifestEntry>,
+    total_downloads: BTreeMap<String, u64>,
+}
+
+impl DownloadManifest {
+    fn path_in(cache_dir: &Path) -> PathBuf {
+        cache_dir.join("downloads").join("manifest.json")
+    }
+
+    /// Loads the manifest kept in `cache_dir`, or starts an empty one.
+    pub fn load(cache_dir: &Path) -> Result<Self> {
+        let path = Self::path_in(cache_dir);
+        if !path.exists() {
+            return Ok(Self {
+                path,
+                entries: BTreeMap::new(),
+                total_downloads: BTreeMap::new(),
+            });
+        }
+
+        let json =
+            fs::read(&path).with_context(|| format!("Failed to read manifest {:?}", path))?;
+        let (entries, total_downloads) = match serde_json::from_slice(&json)
+            .with_context(|| format!("Invalid manifest file {:?}", path))?
+        {
+            StoredManifest::Current {
+                packages,
+                total_downloads,
+            } => (packages, total_downloads),
+            StoredManifest::Legacy(packages) => (packages, BTreeMap::new()),
+        };
+
+        Ok(Self {
+            path,
+            entries: entries
+                .into_iter()
+                .map(|entry| (entry.package.to_lowercase(), entry))
+                .collect(),
+            total_downloads,
+        })
+    }
+
+    pub fn reset(cache_dir: &Path) -> Result<()> {
+        let path = Self::path_in(cache_dir);
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to delete manifest {:?}", path))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn contains(&self, package: &str) -> bool {
+        self.entries.contains_key(&package.to_lowercase())
+    }
+
+    /// Records `entry` and saves the manifest.
+    pub fn record(&mut self, entry: ManifestEntry) -> Result<()> {
+        self.record_all([entry])
+    }
+
+    /// Records several entries and saves the manifest once.
+    pub fn record_all(&mut self, entries: impl IntoIterator<Item = ManifestEntry>) -> Result<()> {
+        self.entries.extend(
+            entries
+                .into_iter()
+                .map(|entry| (entry.package.to_lowercase(), entry)),
+        );
+        self.save()
+    }
+
+    /// The total downloads of `package` fetched by a previous run, if any.
+    pub fn total_downloads(&self, package: &str) -> Option<u64> {
+        self.total_downloads.get(&package.to_lowercase()).copied()
+    }
+
+    /// Records the total downloads of several packages and saves the manifest.
+    pub fn record_total_downloads(
+        &mut self,
+        total_downloads: impl IntoIterator<Item = (String, u64)>,
+    ) -> Result<()> {
+        self.total_downloads.extend(
+            total_downloads
+                .into_iter()
+                .map(|(package, downloads)| (package.to_lowercase(), downloads)),
+        );
+        self.save()
+    }
+
+    /// Writes to a temporary file first, so an interrupted write never leaves a
+    /// truncated manifest behind.
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).context("Failed to create manifest directory")?;
+        }
+
+        let json = serde_json::to_string_pretty(&ManifestFile {
+            packages: self.entries.values().collect(),
+            total_downloads: &self.total_downloads,
+        })?;
+
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, json)
+            .with_context(|| format!("Failed to write manifest {:?}", temp_path))?;
+        fs::rename(&temp_path, &self.path)
+            .with_context(|| format!("Failed to move manifest into place {:?}", self.path))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(package: &str) -> ManifestEntry {
+        ManifestEntry {
+            package: package.to_string(),
+            version: "1.0.0".to_string(),
+            sha1: String::new(),
+            downloaded_at: 0,
+        }
+    }
+
+    #[test]
+    fn packages_are_recorded_case_insensitively() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = DownloadManifest::load(dir.path()).unwrap();
+
+        manifest.record(entry("Acme/Tool")).unwrap();
+
+        assert!(manifest.contains("acme/tool"));
+        assert!(manifest.contains("ACME/TOOL"));
+    }
+
+    #[test]
+    fn recorded_entries_survive_a_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = DownloadManifest::load(dir.path()).unwrap();
+
+        manifest
+            .record_all([entry("acme/one"), entry("Acme/Two")])
+            .unwrap();
+        manifest
+            .record_total_downloads([("acme/one".to_string(), 42)])
+            .unwrap();
+
+        let manifest = DownloadManifest::load(dir.path()).unwrap();
+        assert!(manifest.contains("acme/one"));
+        assert!(manifest.contains("acme/two"));
+        assert!(!manifest.contains("acme/three"));
+        assert_eq!(manifest.total_downloads("Acme/One"), Some(42));
+
+        DownloadManifest::reset(dir.path()).unwrap();
+        assert!(
+            !DownloadManifest::load(dir.path())
+                .unwrap()
+                .contains("acme/one")
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use anyhow::Result;
+use mago_database::file::File;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::params;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::analyzer::AnalysisOptions;
+use crate::cache::CachedMatches;
+use crate::results::KeywordMatch;
+use crate::results::LabelMatch;
+use crate::results::MatchKind;
+use crate::results::Vendor;
+
+const LABEL_KIND: &str = "label";
+
+/// Every identifier and label of every analyzed file, regardless of the keywords
+/// being searched, so that new keywords can be checked without re-parsing.
+#[derive(Debug)]
+pub struct MatchStore {
+    connection: Mutex<Connection>,
+}
+
+impl MatchStore {
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open(path)
+            .with_context(|| format!("Failed to open match cache database {:?}", path))?;
+
+        connection
+            .execute_batch(
+                "PRAGMA journal_mode = WAL;
+                PRAGMA synchronous = NORMAL;
+                CREATE TABLE IF NOT EXISTS files (
+                    file_path TEXT PRIMARY KEY,
+                    file_hash TEXT NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS identifiers (
+                    file_path TEXT NOT NULL,
+                    file_hash TEXT NOT NULL,
+                    identifier TEXT NOT NULL,
+                    identifier_kind TEXT NOT NULL,
+                    is_hard INTEGER NOT NULL,
+                    offset INTEGER NOT NULL,
+                    line INTEGER NOT NULL,
+                    col INTEGER NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS identifiers_by_file
+                    ON identifiers (file_path, file_hash, identifier COLLATE NOCASE);",
+            )
+            .context("Failed to initialize match cache database")?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Returns the matches of `keywords` and `labels` in `file`, or `None` if the
+    /// file has not been stored with this content yet.
+    pub fn get(
+        &self,
+        file: &File,
+        vendor: &Vendor,
+        package: Option<&str>,
+        keywords: &[String],
+        labels: &[String],
+        options: AnalysisOptions,
+    ) -> Option<CachedMatches> {
+        let file_path = file.name.as_ref();
+        let file_hash = content_hash(file);
+        let connection = self.connection.lock().unwrap();
+
+        connection
+            .query_row(
+                "SELECT 1 FROM files WHERE file_path = ?1 AND file_hash = ?2",
+                params![file_path, file_hash],
+                |_| Ok(()),
+            )
+            .optional()
+            .ok()??;
+
+        let names: Vec<String> = keywords
+            .iter()
+            .chain(labels)
+            .map(|name| name.to_lowercase())
+            .collect();
+        let names = serde_json::to_string(&names).ok()?;
+
+        let mut statement = connection
+            .prepare_cached(
+                "SELECT identifier, identifier_kind, is_hard, offset, line FROM identifiers
+                WHERE file_path = ?1 AND file_hash = ?2
+                AND lower(identifier) IN (SELECT value FROM json_each(?3))",
+            )
+            .ok()?;
+
+        let rows = statement
+            .query_map(params![file_path, file_hash, names], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, bool>(2)?,
+                    row.get::<_, u32>(3)?,
+                    row.get::<_, u32>(4)?,
+                ))
+            })
+            .ok()?;
+
+        let mut identifiers = (Vec::new(), Vec::new());
+        for row in rows {
+            let (identifier, kind, is_hard, offset, line) = row.ok()?;
+
+            if kind == LABEL_KIND {
+                identifiers.1.push(LabelMatch {
+                    label: identifier.into(),
+                    vendor: vendor.clone(),
+                });
+            } else {
+                identifiers.0.push(KeywordMatch {
+                    keyword: identifier,
+                    vendor: vendor.clone(),
+                    package: package.map(str::to_string),
+                    file: file_path.to_string(),
+                    line,
+                    kind: MatchKind::from_name(&kind)?,
+                    is_hard,
+                    offset,
+                });
+            }
+        }
+
+        Some(filter_matches(identifiers, keywords, labels, options))
+    }
+
+    /// Replaces the stored identifiers of `file` with `identifiers`.
+    pub fn store(&self, file: &File, identifiers: &CachedMatches) {
+        if let Err(e) = self.try_store(file, identifiers) {
+            tracing::debug!("Failed to store identifiers of {}: {}", file.name, e);
+        }
+    }
+
+    fn try_store(&self, file: &File, identifiers: &CachedMatches) -> rusqlite::Result<()> {
+        let file_path = file.name.as_ref();
+        let file_hash = content_hash(file);
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+
+        transaction.execute(
+            "DELETE FROM identifiers WHERE file_path = ?1",
+            params![file_path],
+        )?;
+        transaction.execute(
+            "INSERT OR REPLACE INTO files (file_path, file_hash) VALUES (?1, ?2)",
+            params![file_path, file_hash],
+        )?;
+
+        {
+            let mut statement = transaction.prepare_cached(
+                "INSERT INTO identifiers
+                (file_path, file_hash, identifier, identifier_kind, is_hard, offset, line, col)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+
+            let (keyword_matches, label_matches) = identifiers;
+            for m in keyword_matches {
+                statement.execute(params![
+                    file_path,
+                    file_hash,
+                    m.keyword,
+                    m.kind.as_str(),
+                    m.is_hard,
+                    m.offset,
+                    m.line,
+                    file.column_number(m.offset) + 1,
+                ])?;
+            }
+
+            // Labels do not carry a position, so they are stored without one.
+            for m in label_matches {
+                statement.execute(params![
+                    file_path, file_hash, &*m.label, LABEL_KIND, false, 0, 0, 0
+                ])?;
+            }
+        }
+
+        transaction.commit()
+    }
+}
+
+/// Narrows down every identifier and label of a file to the ones matching
+/// `keywords` and `labels`, the same way the analyzer would have.
+pub fn filter_matches(
+    (identifiers, all_labels): CachedMatches,
+    keywords: &[String],
+    labels: &[String],
+    options: AnalysisOptions,
+) -> CachedMatches {
+    let matches = |name: &str, expected: &str| {
+        if options.case_sensitive {
+            name == expected
+        } else {
+            name.eq_ignore_ascii_case(expected)
+        }
+    };
+
+    let mut keyword_matches = Vec::new();
+    for identifier in identifiers {
+        let enabled = match identifier.kind {
+            MatchKind::ArrayKey => options.analyze_string_keys,
+            MatchKind::Variable
+            | MatchKind::ClosureUseVariable
+            | MatchKind::ArrowFunctionParameter => options.include_variables,
+            _ => true,
+        };
+
+        if !enabled {
+            continue;
+        }
+
+        if let Some(keyword) = keywords
+            .iter()
+            .find(|keyword| matches(&identifier.keyword, keyword))
+        {
+            keyword_matches.push(KeywordMatch {
+                keyword: keyword.to_string(),
+                ..identifier
+            });
+        }
+    }
+
+    let mut label_matches = Vec::new();
+    for label in all_labels {
+        for label_v in labels {
+            if matches(&label.label, label_v) {
+                label_matches.push(label.clone());
+            }
+        }
+    }
+
+    (keyword_matches, label_matches)
+}
+
+/// SHA-256 rather than `DefaultHasher`, whose output may change between Rust releases,
+/// which would silently invalidate every stored file.
+fn content_hash(file: &File) -> String {
+    format!("{:x}", Sha256::digest(file.contents.as_bytes()))
+}
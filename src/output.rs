@@ -0,0 +1,74 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::io::BufWriter;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use cli_table::ColorChoice;
+use cli_table::TableStruct;
+
+/// Where reports are written: stdout, or a file without ANSI colors.
+pub struct ReportOutput {
+    writer: Box<dyn Write + Send>,
+    color: bool,
+}
+
+impl ReportOutput {
+    /// Colors are left out when `no_color` is set, when the `NO_COLOR` environment
+    /// variable is set (https://no-color.org), or when stdout is not a terminal.
+    pub fn stdout(no_color: bool) -> Self {
+        let color = !no_color
+            && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+            && io::stdout().is_terminal();
+
+        Self {
+            writer: Box::new(io::stdout()),
+            color,
+        }
+    }
+
+    pub fn file(path: &Path) -> Result<Self> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create output directory {:?}", parent))?;
+        }
+
+        let file = fs::File::create(path)
+            .with_context(|| format!("Failed to create output file {:?}", path))?;
+
+        Ok(Self {
+            writer: Box::new(BufWriter::new(file)),
+            color: false,
+        })
+    }
+
+    pub fn print_table(&mut self, table: TableStruct) -> Result<()> {
+        let color_choice = if self.color {
+            ColorChoice::Always
+        } else {
+            ColorChoice::Never
+        };
+
+        let table = table.color_choice(color_choice).display()?;
+        write!(self.writer, "{}", table)?;
+
+        Ok(())
+    }
+}
+
+impl Write for ReportOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,146 @@
+use std::cmp::Ordering;
+
+use anyhow::Context;
+use anyhow::Result;
+
+type Version = [u64; 3];
+
+const OPERATORS: &[&str] = &[">=", "<=", "!=", "==", ">", "<", "=", "^", "~"];
+
+/// Checks whether `version` (e.g. `8.3`) satisfies a Composer-style PHP constraint
+/// such as `^7.4 || ^8.0`, `>=8.1 <8.4`, `8.2.*` or `7.4 - 8.2`.
+pub fn satisfies(constraint: &str, version: &str) -> Result<bool> {
+    let (version, _) =
+        parse_version(version).with_context(|| format!("Invalid PHP version {:?}", version))?;
+
+    let mut any = false;
+    for alternative in constraint.split("||").flat_map(|a| a.split('|')) {
+        if satisfies_conjunction(alternative, version)
+            .with_context(|| format!("Unparseable version constraint {:?}", constraint))?
+        {
+            any = true;
+        }
+    }
+
+    Ok(any)
+}
+
+fn satisfies_conjunction(constraint: &str, version: Version) -> Option<bool> {
+    let constraint = constraint.trim();
+    if constraint.is_empty() {
+        return None;
+    }
+
+    if let Some((lower, upper)) = constraint.split_once(" - ") {
+        let (lower, _) = parse_version(lower)?;
+        let (upper, count) = parse_version(upper)?;
+
+        let below_upper = if count < 3 {
+            version < bump(upper, count - 1)
+        } else {
+            version <= upper
+        };
+
+        return Some(version >= lower && below_upper);
+    }
+
+    let mut terms = Vec::new();
+    let mut pending_operator: Option<&str> = None;
+    for token in constraint.split([' ', ',']).filter(|t| !t.is_empty()) {
+        if OPERATORS.contains(&token) {
+            pending_operator = Some(token);
+            continue;
+        }
+
+        match pending_operator.take() {
+            Some(operator) => terms.push(format!("{}{}", operator, token)),
+            None => terms.push(token.to_string()),
+        }
+    }
+
+    if pending_operator.is_some() || terms.is_empty() {
+        return None;
+    }
+
+    let mut all = true;
+    for term in &terms {
+        all &= satisfies_term(term, version)?;
+    }
+
+    Some(all)
+}
+
+fn satisfies_term(term: &str, version: Version) -> Option<bool> {
+    if term == "*" {
+        return Some(true);
+    }
+
+    for &operator in OPERATORS {
+        let Some(rest) = term.strip_prefix(operator) else {
+            continue;
+        };
+
+        let (bound, count) = parse_version(rest)?;
+        let ordering = version.cmp(&bound);
+
+        return Some(match operator {
+            ">=" => ordering != Ordering::Less,
+            "<=" => ordering != Ordering::Greater,
+            ">" => ordering == Ordering::Greater,
+            "<" => ordering == Ordering::Less,
+            "!=" => ordering != Ordering::Equal,
+            "=" | "==" => ordering == Ordering::Equal,
+            "^" => {
+                let significant = bound
+                    .iter()
+                    .position(|&part| part != 0)
+                    .unwrap_or(count - 1);
+                version >= bound && version < bump(bound, significant.min(count - 1))
+            }
+            "~" => version >= bound && version < bump(bound, count.saturating_sub(2)),
+            _ => unreachable!(),
+        });
+    }
+
+    if let Some(prefix) = term.strip_suffix(".*").or_else(|| term.strip_suffix(".x")) {
+        let (prefix, count) = parse_version(prefix)?;
+
+        return Some(version[..count] == prefix[..count]);
+    }
+
+    let (exact, _) = parse_version(term)?;
+
+    Some(version == exact)
+}
+
+/// Increments the component at `index` and zeroes everything after it.
+fn bump(version: Version, index: usize) -> Version {
+    let mut bumped = [0; 3];
+    bumped[..index].copy_from_slice(&version[..index]);
+    bumped[index] = version[index] + 1;
+    bumped
+}
+
+/// Parses `8`, `8.1`, `v8.1.2` or `8.1.2.0`, ignoring stability suffixes like `-dev` or `@RC`.
+fn parse_version(input: &str) -> Option<(Version, usize)> {
+    let input = input.trim().trim_start_matches(['v', 'V']);
+    let input = input.split(['-', '@']).next()?;
+
+    let mut version = [0; 3];
+    let mut count = 0;
+    for (index, part) in input.split('.').enumerate() {
+        let part: u64 = part.parse().ok()?;
+        if index < 3 {
+            version[index] = part;
+            count += 1;
+        } else if index > 3 {
+            return None;
+        }
+    }
+
+    if count == 0 {
+        return None;
+    }
+
+    Some((version, count))
+}
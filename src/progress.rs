@@ -0,0 +1,43 @@
+use std::io;
+use std::io::IsTerminal;
+
+use tracing::Span;
+use tracing_indicatif::IndicatifLayer;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+use tracing_indicatif::style::ProgressStyle;
+
+const BAR_TEMPLATE: &str =
+    "{span_child_prefix}{span_name} [{bar:40}] {pos}/{len} ({elapsed}, ETA {eta})";
+
+/// Returns the layer drawing progress bars, or `None` when they are disabled
+/// or stderr is not a terminal (e.g. in CI logs).
+pub fn layer<S>(disabled: bool) -> Option<IndicatifLayer<S>>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    if disabled || !io::stderr().is_terminal() {
+        return None;
+    }
+
+    Some(IndicatifLayer::new())
+}
+
+/// Turns the progress indicator of the current span into a bar of `total` items,
+/// and returns the span so it can be advanced from other threads.
+///
+/// Without the progress layer this is a no-op.
+pub fn start(total: usize) -> Span {
+    let span = Span::current();
+
+    if let Ok(style) = ProgressStyle::with_template(BAR_TEMPLATE) {
+        span.pb_set_style(&style.progress_chars("=> "));
+    }
+    span.pb_set_length(total as u64);
+    span.pb_set_position(0);
+
+    span
+}
+
+pub fn advance(span: &Span) {
+    span.pb_inc(1);
+}
@@ -1,36 +1,133 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
-use cli_table::{Cell, Style, Table, format::Justify, print_stdout};
+use anyhow::Context;
+use anyhow::Result;
+use clap::ValueEnum;
+use cli_table::Cell;
+use cli_table::Style;
+use cli_table::Table;
+use cli_table::format::Justify;
+use rayon::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+use crate::output::ReportOutput;
+
+const TOP_PACKAGES: usize = 5;
+/// Package names listed per impact level by [`AnalysisReport::display_drill_down`].
+const DRILL_DOWN_PACKAGES: usize = 20;
+/// Lower bounds of the buckets of [`AnalysisReport::display_histograms`].
+const HISTOGRAM_BUCKETS: &[usize] = &[1, 2, 6, 26, 101, 501];
+const HISTOGRAM_WIDTH: usize = 40;
+/// Examples shown per keyword by [`AnalysisReport::to_html`].
+const HTML_EXAMPLES: usize = 5;
+/// Characters of a source line kept in a [`CodeExample`].
+const EXAMPLE_SNIPPET_WIDTH: usize = 200;
+/// Files below which a report warns that the analysis may not be comprehensive.
+pub const MIN_FILE_WARNING_THRESHOLD: usize = 200_000;
+
+/// Styles of [`AnalysisReport::to_html`]; the impact colors are added from [`ImpactLevel::css_color`].
+const HTML_STYLE: &str = r#"body { font-family: system-ui, sans-serif; margin: 2em; color: #212529; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #dee2e6; padding: 0.4em 0.8em; text-align: left; }
+th { background: #f8f9fa; cursor: pointer; user-select: none; }
+td.number { text-align: right; }
+.impact { font-weight: bold; }
+dt { font-weight: bold; float: left; clear: left; width: 6em; }
+dd { margin-left: 6em; }
+details { margin: 0.5em 0; }
+summary { cursor: pointer; font-weight: bold; }
+pre { background: #f8f9fa; padding: 0.5em; overflow-x: auto; }
+"#;
+
+/// Sorts the table of [`AnalysisReport::to_html`] by the clicked column, using a
+/// cell's `data-sort` value when it has one.
+const HTML_SCRIPT: &str = r#"document.querySelectorAll("th").forEach((header, column) => {
+  header.addEventListener("click", () => {
+    const body = header.closest("table").tBodies[0];
+    const descending = header.dataset.order !== "desc";
+    header.dataset.order = descending ? "desc" : "asc";
+    const value = (row) => row.cells[column].dataset.sort ?? row.cells[column].textContent;
+    Array.from(body.rows)
+      .sort((a, b) => {
+        const [x, y] = [value(a), value(b)];
+        const order = isNaN(x) || isNaN(y) ? x.localeCompare(y) : x - y;
+        return descending ? -order : order;
+      })
+      .forEach((row) => body.appendChild(row));
+  });
+});
+"#;
+
+/// A `(prefix, display name)` pair given with `--custom-vendor`, e.g. `("acme/", "acme")`.
+pub type CustomVendor = (Arc<str>, Arc<str>);
+
+/// Variants are kept in alphabetical order so that sorted vendor sets display alphabetically,
+/// followed by custom vendors sorted by name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Vendor {
-    Symfony,
-    Laravel,
+    Cakephp,
+    Codeigniter4,
     Doctrine,
+    Illuminate,
+    Laminas,
+    Laravel,
+    Nette,
+    Phalcon,
     Phpunit,
+    Spiral,
+    Symfony,
     Twig,
-    Illuminate,
+    Typo3,
+    Yiisoft,
+    Zendframework,
+    /// An in-house vendor given at runtime, holding its display name.
+    Custom(Arc<str>),
     Other,
 }
 
 impl Vendor {
-    pub fn as_str(&self) -> &'static str {
+    /// The display name, e.g. `symfony`; package prefixes add the slash themselves.
+    pub fn as_str(&self) -> &str {
         match self {
-            Vendor::Symfony => "symfony/",
-            Vendor::Laravel => "laravel/",
-            Vendor::Doctrine => "doctrine/",
-            Vendor::Phpunit => "phpunit/",
-            Vendor::Twig => "twig/",
-            Vendor::Illuminate => "illuminate/",
+            Vendor::Symfony => "symfony",
+            Vendor::Laravel => "laravel",
+            Vendor::Doctrine => "doctrine",
+            Vendor::Phpunit => "phpunit",
+            Vendor::Twig => "twig",
+            Vendor::Illuminate => "illuminate",
+            Vendor::Cakephp => "cakephp",
+            Vendor::Codeigniter4 => "codeigniter4",
+            Vendor::Laminas => "laminas",
+            Vendor::Nette => "nette",
+            Vendor::Phalcon => "phalcon",
+            Vendor::Spiral => "spiral",
+            Vendor::Typo3 => "typo3",
+            Vendor::Yiisoft => "yiisoft",
+            Vendor::Zendframework => "zendframework",
+            Vendor::Custom(name) => name,
             Vendor::Other => "",
         }
     }
 
+    /// Custom vendors count as well-known, like the built-in ones.
     pub const fn is_well_known(&self) -> bool {
         !matches!(self, Vendor::Other)
     }
 
-    pub fn from_package(package: &str) -> Self {
+    /// Built-in vendors take precedence over `custom_vendors` with the same prefix.
+    pub fn from_package(package: &str, custom_vendors: &[CustomVendor]) -> Self {
         if package.starts_with("symfony/") {
             Vendor::Symfony
         } else if package.starts_with("laravel/") {
@@ -43,27 +140,237 @@ impl Vendor {
             Vendor::Twig
         } else if package.starts_with("illuminate/") {
             Vendor::Illuminate
+        } else if package.starts_with("cakephp/") {
+            Vendor::Cakephp
+        } else if package.starts_with("codeigniter4/") {
+            Vendor::Codeigniter4
+        } else if package.starts_with("laminas/") {
+            Vendor::Laminas
+        } else if package.starts_with("nette/") {
+            Vendor::Nette
+        } else if package.starts_with("phalcon/") {
+            Vendor::Phalcon
+        } else if package.starts_with("spiral/") {
+            Vendor::Spiral
+        } else if package.starts_with("typo3/") {
+            Vendor::Typo3
+        } else if package.starts_with("yiisoft/") {
+            Vendor::Yiisoft
+        } else if package.starts_with("zendframework/") {
+            Vendor::Zendframework
+        } else if let Some((_, name)) = custom_vendors
+            .iter()
+            .find(|(prefix, _)| package.starts_with(&**prefix))
+        {
+            Vendor::Custom(name.clone())
         } else {
             Vendor::Other
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MatchKind {
+    FunctionName,
+    FunctionCall,
+    FunctionPartialApplication,
+    Identifier,
+    ArrayKey,
+    NamedArgument,
+    Variable,
+    ClassName,
+    ConstantName,
+    InterfaceName,
+    TraitName,
+    EnumName,
+    EnumCaseName,
+    MethodName,
+    MethodCall,
+    AttributeName,
+    NamespaceSegment,
+    HeredocLabel,
+    NowdocLabel,
+    ClosureUseVariable,
+    ArrowFunctionParameter,
+    DeclareDirective,
+    ClassConstantName,
+    ClassConstantAccess,
+    ImportedName,
+    ImportAlias,
+    StaticMethodCall,
+}
+
+impl MatchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchKind::FunctionName => "function name",
+            MatchKind::FunctionCall => "function call",
+            MatchKind::FunctionPartialApplication => "function partial application",
+            MatchKind::Identifier => "identifier",
+            MatchKind::ArrayKey => "array key",
+            MatchKind::NamedArgument => "named argument",
+            MatchKind::Variable => "variable",
+            MatchKind::ClassName => "class name",
+            MatchKind::ConstantName => "constant name",
+            MatchKind::InterfaceName => "interface name",
+            MatchKind::TraitName => "trait name",
+            MatchKind::EnumName => "enum name",
+            MatchKind::EnumCaseName => "enum case name",
+            MatchKind::MethodName => "method name",
+            MatchKind::MethodCall => "method call",
+            MatchKind::AttributeName => "attribute name",
+            MatchKind::NamespaceSegment => "namespace segment",
+            MatchKind::HeredocLabel => "heredoc label",
+            MatchKind::NowdocLabel => "nowdoc label",
+            MatchKind::ClosureUseVariable => "closure use variable",
+            MatchKind::ArrowFunctionParameter => "arrow function parameter",
+            MatchKind::DeclareDirective => "declare directive",
+            MatchKind::ClassConstantName => "class constant name",
+            MatchKind::ClassConstantAccess => "class constant access",
+            MatchKind::ImportedName => "imported name",
+            MatchKind::ImportAlias => "import alias",
+            MatchKind::StaticMethodCall => "static method call",
+        }
+    }
+
+    /// The inverse of [`MatchKind::as_str`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "function name" => Some(MatchKind::FunctionName),
+            "function call" => Some(MatchKind::FunctionCall),
+            "function partial application" => Some(MatchKind::FunctionPartialApplication),
+            "identifier" => Some(MatchKind::Identifier),
+            "array key" => Some(MatchKind::ArrayKey),
+            "named argument" => Some(MatchKind::NamedArgument),
+            "variable" => Some(MatchKind::Variable),
+            "class name" => Some(MatchKind::ClassName),
+            "constant name" => Some(MatchKind::ConstantName),
+            "interface name" => Some(MatchKind::InterfaceName),
+            "trait name" => Some(MatchKind::TraitName),
+            "enum name" => Some(MatchKind::EnumName),
+            "enum case name" => Some(MatchKind::EnumCaseName),
+            "method name" => Some(MatchKind::MethodName),
+            "method call" => Some(MatchKind::MethodCall),
+            "attribute name" => Some(MatchKind::AttributeName),
+            "namespace segment" => Some(MatchKind::NamespaceSegment),
+            "heredoc label" => Some(MatchKind::HeredocLabel),
+            "nowdoc label" => Some(MatchKind::NowdocLabel),
+            "closure use variable" => Some(MatchKind::ClosureUseVariable),
+            "arrow function parameter" => Some(MatchKind::ArrowFunctionParameter),
+            "declare directive" => Some(MatchKind::DeclareDirective),
+            "class constant name" => Some(MatchKind::ClassConstantName),
+            "class constant access" => Some(MatchKind::ClassConstantAccess),
+            "imported name" => Some(MatchKind::ImportedName),
+            "import alias" => Some(MatchKind::ImportAlias),
+            "static method call" => Some(MatchKind::StaticMethodCall),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeywordMatch {
     pub keyword: String,
     pub vendor: Vendor,
+    /// The `vendor/package` name, if the match comes from a package.
+    pub package: Option<String>,
+    pub file: String,
+    /// 1-based line of the match.
+    pub line: u32,
+    pub kind: MatchKind,
     pub is_hard: bool,
+    pub offset: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LabelMatch {
-    // todo: this sohuld be &'arena str, no need to re-allocate
-    pub label: String,
+    /// Shared by every match of the same label in a file, as matches outlive the
+    /// arena the file was parsed into.
+    pub label: Arc<str>,
     pub vendor: Vendor,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// The matches found in a single file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileMatches {
+    pub keyword_matches: Vec<KeywordMatch>,
+    pub label_matches: Vec<LabelMatch>,
+    /// The parser recovered from a syntax error, so matches may be missing.
+    pub has_parse_error: bool,
+    /// The file is not valid UTF-8 and was decoded lossily; known from reading the
+    /// file, so not cached.
+    #[serde(skip)]
+    pub is_non_utf8: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    /// Highest hard impact first
+    #[default]
+    #[value(alias = "impact")]
+    HardImpact,
+    /// Highest soft impact first
+    SoftImpact,
+    /// Most hard matches first
+    HardCount,
+    /// Most soft matches first
+    SoftCount,
+    /// Most matches first
+    TotalCount,
+    /// Alphabetical by keyword
+    #[value(alias = "keyword-name")]
+    Name,
+    /// Most well-known vendors first
+    VendorCount,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable tables
+    #[default]
+    Table,
+    /// GitHub-Flavored Markdown table, e.g. for RFC discussions
+    Markdown,
+    /// Machine-readable JSON document
+    Json,
+    /// Comma-separated values, e.g. for spreadsheets
+    Csv,
+    /// Self-contained HTML page with a sortable table, e.g. for sharing
+    Html,
+}
+
+/// Everything that decides how a report is shown, whatever the format.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOptions {
+    pub format: OutputFormat,
+    pub show_keywords: bool,
+    pub show_labels: bool,
+    pub sort: SortOrder,
+    /// Show at most this many keywords in tables, 0 for all of them.
+    pub top_n: usize,
+    /// Include per-package and per-match detail.
+    pub display: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvMode {
+    KeywordsOnly,
+    LabelsOnly,
+    /// Keywords and labels in one table, told apart by a `Type` column.
+    Combined,
+}
+
+impl CsvMode {
+    pub fn new(show_keywords: bool, show_labels: bool) -> Self {
+        match (show_keywords, show_labels) {
+            (true, false) => CsvMode::KeywordsOnly,
+            (false, true) => CsvMode::LabelsOnly,
+            _ => CsvMode::Combined,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
 pub enum ImpactLevel {
     None,
     Low,
@@ -83,28 +390,175 @@ impl ImpactLevel {
         }
     }
 
-    pub fn calculate(total: usize) -> Self {
-        match total {
-            0 => ImpactLevel::None,
-            1..=25 => ImpactLevel::Low,
-            26..=100 => ImpactLevel::Medium,
-            101..=500 => ImpactLevel::High,
-            _ => ImpactLevel::Critical,
+    pub fn color(&self) -> cli_table::Color {
+        match self {
+            ImpactLevel::None => cli_table::Color::Green,
+            ImpactLevel::Low => cli_table::Color::Cyan,
+            ImpactLevel::Medium => cli_table::Color::Yellow,
+            ImpactLevel::High => cli_table::Color::Red,
+            ImpactLevel::Critical => cli_table::Color::Magenta,
+        }
+    }
+
+    pub fn css_color(&self) -> &'static str {
+        match self {
+            ImpactLevel::None => "#28a745",
+            ImpactLevel::Low => "#17a2b8",
+            ImpactLevel::Medium => "#ffc107",
+            ImpactLevel::High => "#dc3545",
+            ImpactLevel::Critical => "#6f42c1",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            ImpactLevel::None => "✅",
+            ImpactLevel::Low => "ℹ️",
+            ImpactLevel::Medium => "⚠️",
+            ImpactLevel::High => "❌",
+            ImpactLevel::Critical => "🚨",
+        }
+    }
+
+    /// The process exit code used by `--fail-on`, higher for more severe impact.
+    pub fn exit_code(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn calculate(total: usize, thresholds: &ImpactThresholds) -> Self {
+        if total >= thresholds.critical {
+            ImpactLevel::Critical
+        } else if total >= thresholds.high {
+            ImpactLevel::High
+        } else if total >= thresholds.medium {
+            ImpactLevel::Medium
+        } else if total >= thresholds.low {
+            ImpactLevel::Low
+        } else {
+            ImpactLevel::None
         }
     }
 }
 
-#[derive(Debug, Clone)]
+/// The minimum number of matches for each impact level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImpactThresholds {
+    pub low: usize,
+    pub medium: usize,
+    pub high: usize,
+    pub critical: usize,
+}
+
+impl ImpactThresholds {
+    pub fn new(low: usize, medium: usize, high: usize, critical: usize) -> Result<Self> {
+        if !(low < medium && medium < high && high < critical) {
+            anyhow::bail!(
+                "Impact thresholds must be strictly increasing, got low={}, medium={}, high={}, critical={}",
+                low,
+                medium,
+                high,
+                critical
+            );
+        }
+
+        Ok(Self {
+            low,
+            medium,
+            high,
+            critical,
+        })
+    }
+}
+
+impl ImpactThresholds {
+    /// Describes the match counts that result in `level`, e.g. `26 to 100 matches`.
+    fn describe(&self, level: ImpactLevel) -> String {
+        match level {
+            ImpactLevel::None => format!("fewer than {} matches", self.low),
+            ImpactLevel::Low => format!("{} to {} matches", self.low, self.medium - 1),
+            ImpactLevel::Medium => format!("{} to {} matches", self.medium, self.high - 1),
+            ImpactLevel::High => format!("{} to {} matches", self.high, self.critical - 1),
+            ImpactLevel::Critical => format!("{} or more matches", self.critical),
+        }
+    }
+}
+
+impl Default for ImpactThresholds {
+    fn default() -> Self {
+        Self {
+            low: 1,
+            medium: 26,
+            high: 101,
+            critical: 501,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageOccurrence {
+    pub package_name: String,
+    pub soft_count: usize,
+    pub hard_count: usize,
+}
+
+impl PackageOccurrence {
+    pub fn total_count(&self) -> usize {
+        self.soft_count.saturating_add(self.hard_count)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchLocation {
+    /// Shared by the consecutive locations of a file.
+    pub file: Arc<str>,
+    pub line: u32,
+    pub kind: MatchKind,
+    pub is_hard: bool,
+    pub vendor: Vendor,
+}
+
+/// A source line using a keyword, as evidence that reserving it breaks real code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CodeExample {
+    pub file: String,
+    /// 1-based line of the match.
+    pub line: u32,
+    /// The trimmed line, shortened to [`EXAMPLE_SNIPPET_WIDTH`] characters.
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeywordResult {
     pub soft_count: usize,
     pub hard_count: usize,
-    pub well_known_vendors: HashSet<Vendor>,
+    /// Number of files with at least one soft match, however many they contain.
+    #[serde(default)]
+    pub soft_file_count: usize,
+    /// Number of files with at least one hard match, however many they contain.
+    #[serde(default)]
+    pub hard_file_count: usize,
+    /// Number of files with at least one match, soft or hard.
+    #[serde(default)]
+    pub file_count: usize,
+    pub well_known_vendors: BTreeSet<Vendor>,
+    /// Soft and hard match counts per vendor.
+    pub vendor_counts: HashMap<Vendor, (usize, usize)>,
+    /// Soft and hard match counts per package.
+    pub packages: Vec<PackageOccurrence>,
+    /// Where each match was found, if the report keeps locations (see
+    /// [`AnalysisReport::set_keep_locations`]); only serialized on request, and not saved.
+    #[serde(skip)]
+    pub locations: Vec<MatchLocation>,
+    /// Files already counted, with whether the match was hard; only needed while
+    /// matches are added, so not saved.
+    #[serde(skip)]
+    counted_files: HashSet<(String, bool)>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LabelResult {
     pub count: usize,
-    pub well_known_vendors: HashSet<Vendor>,
+    pub well_known_vendors: BTreeSet<Vendor>,
 }
 
 impl KeywordResult {
@@ -112,82 +566,486 @@ impl KeywordResult {
         Self {
             soft_count: 0,
             hard_count: 0,
-            well_known_vendors: HashSet::new(),
+            soft_file_count: 0,
+            hard_file_count: 0,
+            file_count: 0,
+            well_known_vendors: BTreeSet::new(),
+            vendor_counts: HashMap::new(),
+            packages: Vec::new(),
+            locations: Vec::new(),
+            counted_files: HashSet::new(),
         }
     }
 
     pub fn total_count(&self) -> usize {
-        self.soft_count + self.hard_count
+        self.soft_count.saturating_add(self.hard_count)
     }
 
-    pub fn soft_impact(&self) -> ImpactLevel {
-        ImpactLevel::calculate(self.soft_count)
+    pub fn soft_impact(&self, thresholds: &ImpactThresholds) -> ImpactLevel {
+        ImpactLevel::calculate(self.soft_count, thresholds)
     }
 
-    pub fn hard_impact(&self) -> ImpactLevel {
-        ImpactLevel::calculate(self.total_count())
+    pub fn hard_impact(&self, thresholds: &ImpactThresholds) -> ImpactLevel {
+        ImpactLevel::calculate(self.total_count(), thresholds)
     }
 
     pub fn add_match(&mut self, m: &KeywordMatch) {
+        let vendor_counts = self.vendor_counts.entry(m.vendor.clone()).or_default();
         if m.is_hard {
             self.hard_count += 1;
+            vendor_counts.1 += 1;
         } else {
             self.soft_count += 1;
+            vendor_counts.0 += 1;
+        }
+
+        if self.counted_files.insert((m.file.clone(), m.is_hard)) {
+            if !self.counted_files.contains(&(m.file.clone(), !m.is_hard)) {
+                self.file_count += 1;
+            }
+            if m.is_hard {
+                self.hard_file_count += 1;
+            } else {
+                self.soft_file_count += 1;
+            }
         }
 
         if m.vendor.is_well_known() {
-            self.well_known_vendors.insert(m.vendor);
+            self.well_known_vendors.insert(m.vendor.clone());
+        }
+
+        if let Some(package_name) = &m.package {
+            let occurrence = self.package_occurrence(package_name);
+            if m.is_hard {
+                occurrence.hard_count += 1;
+            } else {
+                occurrence.soft_count += 1;
+            }
         }
     }
+
+    /// Records where `m` was found, in addition to counting it with [`KeywordResult::add_match`].
+    pub fn add_location(&mut self, m: &KeywordMatch) {
+        // Matches of a file arrive together, so its name is usually the last one.
+        let file = match self.locations.last() {
+            Some(last) if *last.file == *m.file => Arc::clone(&last.file),
+            _ => Arc::from(m.file.as_str()),
+        };
+
+        self.locations.push(MatchLocation {
+            file,
+            line: m.line,
+            kind: m.kind,
+            is_hard: m.is_hard,
+            vendor: m.vendor.clone(),
+        });
+    }
+
+    fn package_occurrence(&mut self, package_name: &str) -> &mut PackageOccurrence {
+        // Matches of a file arrive together, so the package is usually the last one.
+        match self
+            .packages
+            .iter()
+            .rposition(|occurrence| occurrence.package_name == package_name)
+        {
+            Some(index) => &mut self.packages[index],
+            None => {
+                self.packages.push(PackageOccurrence {
+                    package_name: package_name.to_string(),
+                    soft_count: 0,
+                    hard_count: 0,
+                });
+                self.packages.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// Returns the `count` packages with the most matches.
+    pub fn top_packages(&self, count: usize) -> Vec<&PackageOccurrence> {
+        let mut packages: Vec<_> = self.packages.iter().collect();
+        packages.sort_by(|a, b| {
+            b.total_count()
+                .cmp(&a.total_count())
+                .then_with(|| a.package_name.cmp(&b.package_name))
+        });
+        packages.truncate(count);
+
+        packages
+    }
+
+    pub fn merge(&mut self, other: KeywordResult) {
+        self.soft_count += other.soft_count;
+        self.hard_count += other.hard_count;
+        self.soft_file_count += other.soft_file_count;
+        self.hard_file_count += other.hard_file_count;
+        self.well_known_vendors.extend(other.well_known_vendors);
+
+        // The matches of a file may be split across both results; count it once.
+        let other_files: HashSet<&String> =
+            other.counted_files.iter().map(|(file, _)| file).collect();
+        let shared_files = other_files
+            .into_iter()
+            .filter(|file| {
+                self.counted_files.contains(&((*file).clone(), false))
+                    || self.counted_files.contains(&((*file).clone(), true))
+            })
+            .count();
+        self.file_count += other.file_count - shared_files;
+
+        for (file, is_hard) in other.counted_files {
+            if !self.counted_files.insert((file, is_hard)) {
+                if is_hard {
+                    self.hard_file_count -= 1;
+                } else {
+                    self.soft_file_count -= 1;
+                }
+            }
+        }
+
+        for (vendor, (soft, hard)) in other.vendor_counts {
+            let counts = self.vendor_counts.entry(vendor).or_default();
+            counts.0 += soft;
+            counts.1 += hard;
+        }
+
+        for other_occurrence in other.packages {
+            let occurrence = self.package_occurrence(&other_occurrence.package_name);
+            occurrence.soft_count += other_occurrence.soft_count;
+            occurrence.hard_count += other_occurrence.hard_count;
+        }
+
+        self.locations.extend(other.locations);
+    }
 }
 
 impl LabelResult {
     pub fn new() -> Self {
         Self {
             count: 0,
-            well_known_vendors: HashSet::new(),
+            well_known_vendors: BTreeSet::new(),
         }
     }
 
     pub fn add_match(&mut self, m: &LabelMatch) {
         self.count += 1;
         if m.vendor.is_well_known() {
-            self.well_known_vendors.insert(m.vendor);
+            self.well_known_vendors.insert(m.vendor.clone());
         }
     }
+
+    pub fn merge(&mut self, other: LabelResult) {
+        self.count += other.count;
+        self.well_known_vendors.extend(other.well_known_vendors);
+    }
 }
 
-#[derive(Debug)]
+/// The counts and impact of one keyword, from which every report format is built.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImpactSummary {
+    pub keyword: String,
+    pub soft_count: usize,
+    pub hard_count: usize,
+    /// Number of files with at least one soft match.
+    pub soft_file_count: usize,
+    /// Number of files with at least one hard match.
+    pub hard_file_count: usize,
+    pub soft_impact: ImpactLevel,
+    pub hard_impact: ImpactLevel,
+    /// Number of files with at least one match, soft or hard.
+    pub files_affected: usize,
+    pub packages_affected: usize,
+    pub well_known_vendors: Vec<String>,
+}
+
+impl ImpactSummary {
+    pub fn total_count(&self) -> usize {
+        self.soft_count.saturating_add(self.hard_count)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct KeywordEntry<'a> {
+    keyword: &'a str,
+    #[serde(flatten)]
+    result: &'a KeywordResult,
+    soft_impact: &'static str,
+    hard_impact: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locations: Option<&'a [MatchLocation]>,
+}
+
+#[derive(Debug, Serialize)]
+struct LabelEntry<'a> {
+    label: &'a str,
+    #[serde(flatten)]
+    result: &'a LabelResult,
+    impact: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportEntry<'a> {
+    total_files: usize,
+    total_packages: usize,
+    parse_error_files: usize,
+    non_utf8_files: usize,
+    keywords: Vec<KeywordEntry<'a>>,
+    labels: Vec<LabelEntry<'a>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisReport {
     pub keyword_results: HashMap<String, KeywordResult>,
     pub label_results: HashMap<String, LabelResult>,
     pub total_files: usize,
+    /// Number of distinct `vendor/package` directories the analyzed files belong to.
+    #[serde(default)]
+    pub total_packages: usize,
+    /// The `vendor/package` names of the analyzed packages, so that a later run can skip them.
+    #[serde(default)]
+    pub analyzed_packages: BTreeSet<String>,
+    /// Number of files that could not be fully parsed.
+    #[serde(default)]
+    pub parse_error_files: usize,
+    /// Number of files that were not valid UTF-8, whose invalid bytes were replaced.
+    #[serde(default)]
+    pub non_utf8_files: usize,
+    /// How match counts translate into impact levels; a display setting, so not saved.
+    #[serde(skip)]
+    pub thresholds: ImpactThresholds,
+    /// See [`AnalysisReport::set_min_file_warning_threshold`]; a display setting, so not saved.
+    #[serde(skip, default = "default_min_file_warning_threshold")]
+    min_file_warning_threshold: usize,
+    /// See [`AnalysisReport::set_keep_locations`]; not saved, like the locations.
+    #[serde(skip)]
+    keep_locations: bool,
+}
+
+fn default_min_file_warning_threshold() -> usize {
+    MIN_FILE_WARNING_THRESHOLD
 }
 
 impl AnalysisReport {
-    pub fn new(total_files: usize) -> Self {
+    pub fn new(total_files: usize, analyzed_packages: BTreeSet<String>) -> Self {
         Self {
             keyword_results: HashMap::new(),
             label_results: HashMap::new(),
             total_files,
+            total_packages: analyzed_packages.len(),
+            analyzed_packages,
+            parse_error_files: 0,
+            non_utf8_files: 0,
+            thresholds: ImpactThresholds::default(),
+            min_file_warning_threshold: MIN_FILE_WARNING_THRESHOLD,
+            keep_locations: false,
         }
     }
 
+    /// Sets whether matches added from now on keep where they were found, which
+    /// `--display`, `--show-cooccurrence`, `--files-with-matches` and the HTML examples
+    /// need. Off by default, as a location per match adds up on large corpora.
+    pub fn set_keep_locations(&mut self, keep_locations: bool) {
+        self.keep_locations = keep_locations;
+    }
+
+    /// Sets the number of files below which the table warns that the analysis may
+    /// not be comprehensive; 0 disables the warning.
+    pub fn set_min_file_warning_threshold(&mut self, n: usize) {
+        self.min_file_warning_threshold = n;
+    }
+
     pub fn add_keyword_matches(&mut self, matches: Vec<KeywordMatch>) {
-        for m in matches {
+        let keep_locations = self.keep_locations;
+        let results = matches
+            .into_par_iter()
+            .fold(
+                HashMap::new,
+                |mut results: HashMap<String, KeywordResult>, m| {
+                    let result = results
+                        .entry(m.keyword.clone())
+                        .or_insert_with(KeywordResult::new);
+                    result.add_match(&m);
+                    if keep_locations {
+                        result.add_location(&m);
+                    }
+                    results
+                },
+            )
+            .reduce(HashMap::new, |mut left, right| {
+                for (keyword, result) in right {
+                    left.entry(keyword)
+                        .or_insert_with(KeywordResult::new)
+                        .merge(result);
+                }
+                left
+            });
+
+        for (keyword, result) in results {
             self.keyword_results
-                .entry(m.keyword.clone())
+                .entry(keyword)
                 .or_insert_with(KeywordResult::new)
-                .add_match(&m);
+                .merge(result);
+        }
+    }
+
+    pub fn add_keyword_match(&mut self, m: &KeywordMatch) {
+        let result = self
+            .keyword_results
+            .entry(m.keyword.clone())
+            .or_insert_with(KeywordResult::new);
+        result.add_match(m);
+        if self.keep_locations {
+            result.add_location(m);
+        }
+    }
+
+    /// Adds the matches of one analyzed file, which [`AnalysisReport::new`] already counted.
+    pub fn add_file_matches(&mut self, matches: FileMatches) {
+        for m in &matches.keyword_matches {
+            self.add_keyword_match(m);
+        }
+        self.add_label_matches(matches.label_matches);
+        if matches.has_parse_error {
+            self.parse_error_files += 1;
+        }
+        if matches.is_non_utf8 {
+            self.non_utf8_files += 1;
         }
     }
 
     pub fn add_label_matches(&mut self, matches: Vec<LabelMatch>) {
         for m in matches {
+            match self.label_results.get_mut(&*m.label) {
+                Some(result) => result.add_match(&m),
+                None => {
+                    let mut result = LabelResult::new();
+                    result.add_match(&m);
+                    self.label_results.insert(m.label.to_string(), result);
+                }
+            }
+        }
+    }
+
+    /// Combines the results of another analysis, e.g. of a different range of packages.
+    pub fn merge(&mut self, other: AnalysisReport) {
+        self.total_files += other.total_files;
+        self.total_packages += other.total_packages;
+        self.analyzed_packages.extend(other.analyzed_packages);
+        self.parse_error_files += other.parse_error_files;
+        self.non_utf8_files += other.non_utf8_files;
+
+        for (keyword, result) in other.keyword_results {
+            self.keyword_results
+                .entry(keyword)
+                .or_insert_with(KeywordResult::new)
+                .merge(result);
+        }
+
+        for (label, result) in other.label_results {
             self.label_results
-                .entry(m.label.clone())
+                .entry(label)
                 .or_insert_with(LabelResult::new)
-                .add_match(&m);
+                .merge(result);
+        }
+    }
+
+    /// Returns the files with at least one keyword match, in path order.
+    pub fn matched_files(&self) -> BTreeSet<&str> {
+        self.keyword_results
+            .values()
+            .flat_map(|result| &result.locations)
+            .map(|location| &*location.file)
+            .collect()
+    }
+
+    /// Returns up to `n` lines using `keyword`, taken from as many vendors, and then
+    /// files, as possible. The lines are read back from the analyzed files, so files
+    /// that are gone are skipped.
+    pub fn collect_examples(&self, keyword: &str, n: usize) -> Vec<CodeExample> {
+        let Some(result) = self.keyword_results.get(keyword) else {
+            return Vec::new();
+        };
+
+        let mut locations: Vec<&MatchLocation> = result.locations.iter().collect();
+        locations.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+
+        // The first location of each vendor, then the first of each remaining file.
+        let mut vendors = HashSet::new();
+        let mut files = HashSet::new();
+        let mut candidates = Vec::new();
+        for &location in &locations {
+            if vendors.insert(&location.vendor) {
+                files.insert(&*location.file);
+                candidates.push(location);
+            }
+        }
+        for &location in &locations {
+            if files.insert(&*location.file) {
+                candidates.push(location);
+            }
+        }
+
+        candidates
+            .into_iter()
+            .filter_map(|location| {
+                let contents = fs::read(&*location.file).ok()?;
+                let contents = String::from_utf8_lossy(&contents);
+                let line = contents
+                    .lines()
+                    .nth(location.line.checked_sub(1)? as usize)?
+                    .trim();
+
+                let mut snippet: String = line.chars().take(EXAMPLE_SNIPPET_WIDTH).collect();
+                if snippet.len() < line.len() {
+                    snippet.push('…');
+                }
+
+                Some(CodeExample {
+                    file: location.file.to_string(),
+                    line: location.line,
+                    snippet,
+                })
+            })
+            .take(n)
+            .collect()
+    }
+
+    /// Compares this report against an older `baseline` report.
+    pub fn diff(&self, baseline: &AnalysisReport) -> ReportDiff {
+        let empty = KeywordResult::new();
+        let keywords: BTreeSet<&String> = self
+            .keyword_results
+            .keys()
+            .chain(baseline.keyword_results.keys())
+            .collect();
+
+        let keyword_diffs = keywords
+            .into_iter()
+            .map(|keyword| {
+                let current = self.keyword_results.get(keyword).unwrap_or(&empty);
+                let previous = baseline.keyword_results.get(keyword).unwrap_or(&empty);
+
+                let diff = KeywordDiff {
+                    soft_delta: current.soft_count as isize - previous.soft_count as isize,
+                    hard_delta: current.hard_count as isize - previous.hard_count as isize,
+                    added_vendors: current
+                        .well_known_vendors
+                        .difference(&previous.well_known_vendors)
+                        .cloned()
+                        .collect(),
+                    removed_vendors: previous
+                        .well_known_vendors
+                        .difference(&current.well_known_vendors)
+                        .cloned()
+                        .collect(),
+                };
+
+                (keyword.clone(), diff)
+            })
+            .collect();
+
+        ReportDiff {
+            total_files_delta: self.total_files as isize - baseline.total_files as isize,
+            keyword_diffs,
         }
     }
 
@@ -199,151 +1057,830 @@ impl AnalysisReport {
         }
     }
 
-    pub fn should_warn_low_file_count(&self) -> bool {
-        self.total_files < 200_000
-    }
+    /// Returns keyword → (soft_count, hard_count) restricted to matches from the given vendor.
+    pub fn keyword_results_for_vendor(&self, vendor: &Vendor) -> HashMap<&str, (usize, usize)> {
+        self.keyword_results
+            .iter()
+            .map(|(keyword, result)| {
+                let counts = result
+                    .vendor_counts
+                    .get(vendor)
+                    .copied()
+                    .unwrap_or_default();
 
-    fn wrap_text(text: &str, max_width: usize) -> String {
-        if text.len() <= max_width {
-            return text.to_string();
-        }
+                (keyword.as_str(), counts)
+            })
+            .collect()
+    }
 
-        let mut result = String::new();
-        let mut current_line = String::new();
+    /// Returns the number of files with matches of both keywords, for each pair of
+    /// keywords sharing at least one file. Pairs are ordered, `a < b`.
+    ///
+    /// Relies on match locations, so reports that do not keep them, including saved
+    /// reports, have no co-occurrences.
+    pub fn cooccurrence_matrix(&self) -> HashMap<(String, String), usize> {
+        let mut keyword_files: Vec<(&String, HashSet<&str>)> = self
+            .keyword_results
+            .iter()
+            .map(|(keyword, result)| {
+                let files = result
+                    .locations
+                    .iter()
+                    .map(|location| &*location.file)
+                    .collect();
 
-        for word in text.split(", ") {
-            let word_with_sep = if current_line.is_empty() {
-                word.to_string()
-            } else {
-                format!(", {}", word)
-            };
+                (keyword, files)
+            })
+            .collect();
+        keyword_files.sort_by(|a, b| a.0.cmp(b.0));
 
-            if current_line.len() + word_with_sep.len() <= max_width {
-                current_line.push_str(&word_with_sep);
-            } else {
-                if !result.is_empty() {
-                    result.push('\n');
+        let mut matrix = HashMap::new();
+        for (index, (a, a_files)) in keyword_files.iter().enumerate() {
+            for (b, b_files) in &keyword_files[index + 1..] {
+                let shared = a_files.intersection(b_files).count();
+                if shared > 0 {
+                    matrix.insert(((*a).clone(), (*b).clone()), shared);
                 }
-                result.push_str(&current_line);
-                current_line = word.to_string();
             }
         }
 
-        if !current_line.is_empty() {
-            if !result.is_empty() {
-                result.push('\n');
+        matrix
+    }
+
+    /// Returns vendor → keyword → (soft_count, hard_count) for well-known vendors.
+    pub fn vendor_keyword_matrix(&self) -> HashMap<Vendor, HashMap<String, (usize, usize)>> {
+        let mut matrix: HashMap<Vendor, HashMap<String, (usize, usize)>> = HashMap::new();
+
+        for (keyword, result) in &self.keyword_results {
+            for (vendor, counts) in &result.vendor_counts {
+                if vendor.is_well_known() {
+                    matrix
+                        .entry(vendor.clone())
+                        .or_default()
+                        .insert(keyword.clone(), *counts);
+                }
             }
-            result.push_str(&current_line);
         }
 
-        result
+        matrix
     }
 
-    fn create_impact_cell(impact: ImpactLevel) -> cli_table::CellStruct {
-        match impact {
-            ImpactLevel::None => impact
-                .as_str()
-                .cell()
-                .foreground_color(Some(cli_table::Color::Green)),
-            ImpactLevel::Low => impact
-                .as_str()
-                .cell()
-                .foreground_color(Some(cli_table::Color::Cyan)),
-            ImpactLevel::Medium => impact
-                .as_str()
-                .cell()
-                .foreground_color(Some(cli_table::Color::Yellow)),
-            ImpactLevel::High => impact
-                .as_str()
-                .cell()
-                .foreground_color(Some(cli_table::Color::Red)),
-            ImpactLevel::Critical => impact
-                .as_str()
-                .cell()
-                .foreground_color(Some(cli_table::Color::Magenta))
-                .bold(true),
-        }
-    }
-
-    pub fn display_table(&self, show_keywords: bool, show_labels: bool) {
-        if self.should_warn_low_file_count() {
-            eprintln!(
-                "\n⚠️  WARNING: Only analyzed {} files (less than 200,000 recommended)",
-                self.total_files
-            );
-            eprintln!(
-                "   Consider increasing --max to scan more packages for a comprehensive analysis.\n"
-            );
-        }
+    /// Returns the highest hard impact across all keywords.
+    pub fn max_impact(&self) -> ImpactLevel {
+        self.keyword_results
+            .values()
+            .map(|result| result.hard_impact(&self.thresholds))
+            .max()
+            .unwrap_or(ImpactLevel::None)
+    }
 
-        if show_keywords {
-            let mut keyword_data: Vec<_> = self
-                .keyword_results
-                .iter()
-                .map(|(keyword, result)| {
-                    let soft_impact = result.soft_impact();
-                    let hard_impact = result.hard_impact();
-                    (keyword.clone(), result, soft_impact, hard_impact)
-                })
-                .collect();
+    /// Drops every keyword whose hard impact is below `min`.
+    pub fn filter_by_severity(&mut self, min: ImpactLevel) {
+        let thresholds = self.thresholds;
+        self.keyword_results
+            .retain(|_, result| result.hard_impact(&thresholds) >= min);
+    }
 
-            if keyword_data.is_empty() {
-                tracing::info!("No keywords match found in the analyzed packages.");
-                return;
-            }
+    /// Returns true if any keyword has critical hard impact.
+    pub fn is_critical(&self) -> bool {
+        self.max_impact() == ImpactLevel::Critical
+    }
 
-            keyword_data.sort_by(|a, b| {
-                b.3.cmp(&a.3)
-                    .then_with(|| b.1.total_count().cmp(&a.1.total_count()))
-                    .then_with(|| a.0.cmp(&b.0))
-            });
+    /// Returns the soft and hard match counts summed over every keyword, for the
+    /// footer of the keyword table.
+    pub fn total_counts(&self) -> (usize, usize) {
+        self.keyword_results
+            .values()
+            .fold((0, 0), |(soft, hard), result| {
+                (soft + result.soft_count, hard + result.hard_count)
+            })
+    }
 
-            let mut keyboard_rows = Vec::new();
-            for (keyword, result, soft_impact, hard_impact) in keyword_data {
-                let well_known_str = if result.well_known_vendors.is_empty() {
-                    "-".to_string()
-                } else {
-                    let mut vendors: Vec<_> = result
-                        .well_known_vendors
-                        .iter()
-                        .map(|v| v.as_str().trim_end_matches('/'))
-                        .collect();
-                    vendors.sort();
-                    Self::wrap_text(&vendors.join(", "), 60)
-                };
+    /// Counts the packages using `keyword` a number of times within each bucket, where
+    /// `buckets` are ascending lower bounds: `[1, 6, 26]` gives `1..6`, `6..26` and
+    /// `26..usize::MAX`. Packages using it fewer times than the first bound are left out.
+    pub fn symbol_histogram(&self, keyword: &str, buckets: &[usize]) -> Vec<(Range<usize>, usize)> {
+        let mut histogram: Vec<_> = buckets
+            .iter()
+            .enumerate()
+            .map(|(index, &start)| {
+                let end = buckets.get(index + 1).copied().unwrap_or(usize::MAX);
+                (start..end, 0)
+            })
+            .collect();
 
-                keyboard_rows.push(vec![
-                    keyword.cell().bold(true),
-                    result.soft_count.cell().justify(Justify::Right),
-                    result.hard_count.cell().justify(Justify::Right),
-                    Self::create_impact_cell(soft_impact),
-                    Self::create_impact_cell(hard_impact),
-                    well_known_str.cell(),
-                ]);
+        let Some(result) = self.keyword_results.get(keyword) else {
+            return histogram;
+        };
+
+        for occurrence in &result.packages {
+            let count = occurrence.total_count();
+            if let Some((_, packages)) = histogram
+                .iter_mut()
+                .find(|(range, _)| range.contains(&count))
+            {
+                *packages += 1;
             }
+        }
 
-            let table = keyboard_rows.table().title(vec![
-                "Keyword".cell().bold(true),
-                "Soft".cell().bold(true),
-                "Hard".cell().bold(true),
-                "Soft Impact".cell().bold(true),
-                "Hard Impact".cell().bold(true),
-                "Well-Known Vendors".cell().bold(true),
-            ]);
+        histogram
+    }
+
+    /// Returns the number of distinct packages with at least one match of `keyword`.
+    pub fn packages_affected(&self, keyword: &str) -> usize {
+        self.keyword_results
+            .get(keyword)
+            .map_or(0, |result| result.packages.len())
+    }
+
+    /// Groups the packages matching `keyword` by the impact of their own matches, e.g.
+    /// a package with 200 matches is `High` whatever the total across packages. Each
+    /// group lists the packages with the most matches first.
+    pub fn packages_by_impact(&self, keyword: &str) -> BTreeMap<ImpactLevel, Vec<String>> {
+        let mut packages_by_impact: BTreeMap<ImpactLevel, Vec<String>> = BTreeMap::new();
+        let Some(result) = self.keyword_results.get(keyword) else {
+            return packages_by_impact;
+        };
 
-            let _ = print_stdout(table);
+        for occurrence in result.top_packages(result.packages.len()) {
+            let impact = ImpactLevel::calculate(occurrence.total_count(), &self.thresholds);
+            packages_by_impact
+                .entry(impact)
+                .or_default()
+                .push(occurrence.package_name.clone());
         }
 
-        if show_labels {
-            if show_keywords {
-                println!();
-            }
+        packages_by_impact
+    }
 
-            let mut label_data: Vec<_> = self.label_results.iter().collect();
-            if label_data.is_empty() {
-                tracing::info!("No labels match found in the analyzed packages.");
-                return;
-            }
+    /// Returns the `n` packages with the most matches of `keyword`, soft and hard
+    /// combined, as `(package_name, soft_count, hard_count)`.
+    pub fn top_packages_by_keyword(&self, keyword: &str, n: usize) -> Vec<(&str, usize, usize)> {
+        let Some(result) = self.keyword_results.get(keyword) else {
+            return Vec::new();
+        };
+
+        result
+            .top_packages(n)
+            .into_iter()
+            .map(|occurrence| {
+                (
+                    occurrence.package_name.as_str(),
+                    occurrence.soft_count,
+                    occurrence.hard_count,
+                )
+            })
+            .collect()
+    }
+
+    /// Formats `file_count` as a share of all analyzed files, e.g. `0.25%`.
+    fn percent_of_files(&self, file_count: usize) -> String {
+        if self.total_files == 0 {
+            return "0.00%".to_string();
+        }
+
+        format!(
+            "{:.2}%",
+            file_count as f64 / self.total_files as f64 * 100.0
+        )
+    }
+
+    pub fn should_warn_low_file_count(&self) -> bool {
+        self.total_files < self.min_file_warning_threshold
+    }
+
+    /// Joins `items` with ", " into lines of at most `max_width` characters,
+    /// breaking between items, or inside an item that is longer than a line.
+    fn wrap_list(items: &[&str], max_width: usize) -> String {
+        let mut lines = Vec::new();
+        let mut current_line = String::new();
+        let mut current_width = 0;
+
+        for item in items {
+            let width = item.chars().count();
+            if current_width > 0 && current_width + 2 + width <= max_width {
+                current_line.push_str(", ");
+                current_line.push_str(item);
+                current_width += 2 + width;
+                continue;
+            }
+
+            if current_width > 0 {
+                lines.push(std::mem::take(&mut current_line));
+            }
+
+            let chars: Vec<char> = item.chars().collect();
+            let mut chunks: Vec<String> = chars
+                .chunks(max_width.max(1))
+                .map(String::from_iter)
+                .collect();
+            current_line = chunks.pop().unwrap_or_default();
+            current_width = current_line.chars().count();
+            lines.extend(chunks);
+        }
+
+        if !current_line.is_empty() {
+            lines.push(current_line);
+        }
+
+        lines.join("\n")
+    }
+
+    fn create_impact_cell(impact: ImpactLevel) -> cli_table::CellStruct {
+        impact
+            .as_str()
+            .cell()
+            .foreground_color(Some(impact.color()))
+            .bold(impact == ImpactLevel::Critical)
+    }
+
+    pub fn sorted_keyword_results(&self, sort: SortOrder) -> Vec<(&String, &KeywordResult)> {
+        let mut keyword_data: Vec<_> = self.keyword_results.iter().collect();
+
+        keyword_data.sort_by(|a, b| {
+            self.sort_key(b.1, sort)
+                .cmp(&self.sort_key(a.1, sort))
+                .then_with(|| a.0.cmp(b.0))
+        });
+
+        keyword_data
+    }
+
+    /// Returns the counts and impact of every keyword, highest hard impact first.
+    pub fn impact_summary(&self) -> Vec<ImpactSummary> {
+        self.sorted_impact_summary(SortOrder::HardImpact)
+    }
+
+    /// Like [`AnalysisReport::impact_summary`], in the given order.
+    pub fn sorted_impact_summary(&self, sort: SortOrder) -> Vec<ImpactSummary> {
+        self.sorted_keyword_results(sort)
+            .into_iter()
+            .map(|(keyword, result)| ImpactSummary {
+                keyword: keyword.clone(),
+                soft_count: result.soft_count,
+                hard_count: result.hard_count,
+                soft_file_count: result.soft_file_count,
+                hard_file_count: result.hard_file_count,
+                soft_impact: result.soft_impact(&self.thresholds),
+                hard_impact: result.hard_impact(&self.thresholds),
+                files_affected: result.file_count,
+                packages_affected: result.packages.len(),
+                well_known_vendors: result
+                    .well_known_vendors
+                    .iter()
+                    .map(|vendor| vendor.as_str().to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Rows are sorted by this key, largest first, and then alphabetically.
+    fn sort_key(&self, result: &KeywordResult, sort: SortOrder) -> (usize, usize) {
+        match sort {
+            SortOrder::HardImpact => (
+                result.hard_impact(&self.thresholds) as usize,
+                result.total_count(),
+            ),
+            SortOrder::SoftImpact => (
+                result.soft_impact(&self.thresholds) as usize,
+                result.soft_count,
+            ),
+            SortOrder::HardCount => (result.hard_count, 0),
+            SortOrder::SoftCount => (result.soft_count, 0),
+            SortOrder::TotalCount => (result.total_count(), 0),
+            SortOrder::Name => (0, 0),
+            SortOrder::VendorCount => (result.well_known_vendors.len(), result.total_count()),
+        }
+    }
+
+    fn to_entry(&self, display: bool, sort: SortOrder) -> ReportEntry<'_> {
+        let keywords = self
+            .sorted_keyword_results(sort)
+            .into_iter()
+            .map(|(keyword, result)| KeywordEntry {
+                keyword,
+                result,
+                soft_impact: result.soft_impact(&self.thresholds).as_str(),
+                hard_impact: result.hard_impact(&self.thresholds).as_str(),
+                locations: display.then_some(result.locations.as_slice()),
+            })
+            .collect();
+
+        let mut labels: Vec<_> = self
+            .label_results
+            .iter()
+            .map(|(label, result)| LabelEntry {
+                label,
+                result,
+                impact: ImpactLevel::calculate(result.count, &self.thresholds).as_str(),
+            })
+            .collect();
+        labels.sort_by(|a, b| a.label.cmp(b.label));
+
+        ReportEntry {
+            total_files: self.total_files,
+            total_packages: self.total_packages,
+            parse_error_files: self.parse_error_files,
+            non_utf8_files: self.non_utf8_files,
+            keywords,
+            labels,
+        }
+    }
+
+    /// Saves the report so it can be displayed again with [`AnalysisReport::load`].
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).context("Failed to serialize report")?;
+
+        fs::write(path, json).with_context(|| format!("Failed to write report to {:?}", path))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json =
+            fs::read(path).with_context(|| format!("Failed to read report from {:?}", path))?;
+
+        serde_json::from_slice(&json).with_context(|| format!("Invalid report file {:?}", path))
+    }
+
+    /// Writes the report to `out` in the requested format.
+    pub fn render(&self, out: &mut ReportOutput, options: &RenderOptions) -> Result<()> {
+        match options.format {
+            OutputFormat::Table => self.display_table(
+                out,
+                options.show_keywords,
+                options.show_labels,
+                options.sort,
+                options.top_n,
+                options.display,
+            )?,
+            OutputFormat::Markdown => write!(out, "{}", self.to_markdown(options.sort))?,
+            OutputFormat::Html => write!(out, "{}", self.to_html())?,
+            OutputFormat::Json => {
+                writeln!(out, "{}", self.to_json(options.display, options.sort)?)?
+            }
+            OutputFormat::Csv => self.to_csv_writer(
+                out,
+                CsvMode::new(options.show_keywords, options.show_labels),
+                options.sort,
+            )?,
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the report, with keywords in `sort` order; `display` also includes the
+    /// location of every match.
+    pub fn to_json(&self, display: bool, sort: SortOrder) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.to_entry(display, sort))?)
+    }
+
+    /// Formats the keyword table as GitHub-Flavored Markdown, with the same columns
+    /// as [`AnalysisReport::display_table`].
+    pub fn to_markdown(&self, sort: SortOrder) -> String {
+        let mut markdown = format!("Analyzed **{}** files.\n\n", self.total_files);
+        markdown.push_str(
+            "| Keyword | Soft | Hard | Soft Files | Hard Files | \
+             Soft Impact | Hard Impact | Well-Known Vendors |\n",
+        );
+        markdown.push_str("| :--- | ---: | ---: | ---: | ---: | :---: | :---: | :--- |\n");
+
+        for summary in self.sorted_impact_summary(sort) {
+            markdown.push_str(&format!(
+                "| **{}** | {} | {} | {} | {} | {} | {} | {} |\n",
+                summary.keyword,
+                summary.soft_count,
+                summary.hard_count,
+                summary.soft_file_count,
+                summary.hard_file_count,
+                Self::markdown_impact(summary.soft_impact),
+                Self::markdown_impact(summary.hard_impact),
+                Self::vendor_list(&summary.well_known_vendors)
+            ));
+        }
+
+        let (soft_total, hard_total) = self.total_counts();
+        markdown.push_str(&format!(
+            "| **TOTAL** | **{}** | **{}** | - | - | - | - | - |\n",
+            soft_total, hard_total
+        ));
+
+        markdown
+    }
+
+    fn markdown_impact(impact: ImpactLevel) -> String {
+        format!("{} `{}`", impact.emoji(), impact.as_str())
+    }
+
+    /// Formats the keyword table as a single HTML page with embedded styles, with the
+    /// same columns as [`AnalysisReport::display_table`].
+    ///
+    /// Rows are sorted by hard impact, so the page reads fine without JavaScript; with
+    /// it, clicking a column header sorts by that column.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Keyword Impact Report</title>\n<style>\n",
+        );
+        html.push_str(HTML_STYLE);
+        for &level in ImpactLevel::value_variants() {
+            html.push_str(&format!(
+                ".impact-{} {{ color: {}; }}\n",
+                level.as_str().to_lowercase(),
+                level.css_color()
+            ));
+        }
+        html.push_str("</style>\n</head>\n<body>\n<h1>Keyword Impact Report</h1>\n");
+
+        html.push_str(&format!(
+            "<p>Generated {}. Analyzed {} files from {} packages.</p>\n",
+            format_utc(SystemTime::now()),
+            self.total_files,
+            self.total_packages
+        ));
+        if self.parse_error_files > 0 {
+            html.push_str(&format!(
+                "<p>Note: {} files had parse errors and may have incomplete results.</p>\n",
+                self.parse_error_files
+            ));
+        }
+        if self.non_utf8_files > 0 {
+            html.push_str(&format!(
+                "<p>Note: {} files were not valid UTF-8, so matches spanning invalid bytes may be missing.</p>\n",
+                self.non_utf8_files
+            ));
+        }
+
+        html.push_str(
+            "<table>\n<thead>\n<tr><th>Keyword</th><th>Soft</th><th>Hard</th>\
+             <th>Soft Files</th><th>Hard Files</th><th>Soft Impact</th><th>Hard Impact</th>\
+             <th>Well-Known Vendors</th></tr>\n</thead>\n<tbody>\n",
+        );
+        let summaries = self.impact_summary();
+        for summary in &summaries {
+            html.push_str(&format!(
+                "<tr><td><strong>{}</strong></td><td class=\"number\">{}</td>\
+                 <td class=\"number\">{}</td><td class=\"number\">{}</td>\
+                 <td class=\"number\">{}</td>{}{}<td>{}</td></tr>\n",
+                escape_html(&summary.keyword),
+                summary.soft_count,
+                summary.hard_count,
+                summary.soft_file_count,
+                summary.hard_file_count,
+                Self::html_impact(summary.soft_impact),
+                Self::html_impact(summary.hard_impact),
+                escape_html(&Self::vendor_list(&summary.well_known_vendors))
+            ));
+        }
+        html.push_str("</tbody>\n</table>\n");
+
+        let examples: Vec<_> = summaries
+            .iter()
+            .map(|summary| {
+                let examples = self.collect_examples(&summary.keyword, HTML_EXAMPLES);
+                (&summary.keyword, examples)
+            })
+            .filter(|(_, examples)| !examples.is_empty())
+            .collect();
+        if !examples.is_empty() {
+            html.push_str("<h2>Examples</h2>\n");
+            for (keyword, examples) in examples {
+                html.push_str(&format!(
+                    "<details>\n<summary>{}</summary>\n",
+                    escape_html(keyword)
+                ));
+                for example in examples {
+                    html.push_str(&format!(
+                        "<p><code>{}:{}</code></p>\n<pre>{}</pre>\n",
+                        escape_html(&example.file),
+                        example.line,
+                        escape_html(&example.snippet)
+                    ));
+                }
+                html.push_str("</details>\n");
+            }
+        }
+
+        html.push_str("<h2>Impact levels</h2>\n<dl>\n");
+        for &level in ImpactLevel::value_variants() {
+            html.push_str(&format!(
+                "<dt class=\"impact-{}\">{}</dt><dd>{}</dd>\n",
+                level.as_str().to_lowercase(),
+                level.as_str(),
+                self.thresholds.describe(level)
+            ));
+        }
+        html.push_str(
+            "</dl>\n<p>Soft impact counts soft matches only; hard impact counts all matches.</p>\n",
+        );
+
+        html.push_str("<script>\n");
+        html.push_str(HTML_SCRIPT);
+        html.push_str("</script>\n</body>\n</html>\n");
+
+        html
+    }
+
+    /// Impact cells sort by level rather than alphabetically.
+    fn html_impact(impact: ImpactLevel) -> String {
+        format!(
+            "<td class=\"impact impact-{}\" data-sort=\"{}\">{}</td>",
+            impact.as_str().to_lowercase(),
+            impact as u8,
+            impact.as_str()
+        )
+    }
+
+    /// Writes the report as CSV, using the same column headers as [`AnalysisReport::display_table`],
+    /// with keywords in `sort` order.
+    pub fn to_csv_writer<W: io::Write>(
+        &self,
+        writer: W,
+        mode: CsvMode,
+        sort: SortOrder,
+    ) -> Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        let entry = self.to_entry(false, sort);
+        let summaries = self.sorted_impact_summary(sort);
+
+        match mode {
+            CsvMode::KeywordsOnly => {
+                writer.write_record([
+                    "Keyword",
+                    "Soft",
+                    "Hard",
+                    "Soft Files",
+                    "Hard Files",
+                    "Soft Impact",
+                    "Hard Impact",
+                    "Well-Known Vendors",
+                ])?;
+                for summary in &summaries {
+                    writer.write_record([
+                        summary.keyword.clone(),
+                        summary.soft_count.to_string(),
+                        summary.hard_count.to_string(),
+                        summary.soft_file_count.to_string(),
+                        summary.hard_file_count.to_string(),
+                        summary.soft_impact.as_str().to_string(),
+                        summary.hard_impact.as_str().to_string(),
+                        summary.well_known_vendors.join(", "),
+                    ])?;
+                }
+
+                let (soft_total, hard_total) = self.total_counts();
+                writer.write_record([
+                    "TOTAL".to_string(),
+                    soft_total.to_string(),
+                    hard_total.to_string(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ])?;
+            }
+            CsvMode::LabelsOnly => {
+                writer.write_record(["Label", "Count", "Well-Known Vendors"])?;
+                for label in &entry.labels {
+                    writer.write_record([
+                        label.label.to_string(),
+                        label.result.count.to_string(),
+                        Self::vendor_names(&label.result.well_known_vendors),
+                    ])?;
+                }
+            }
+            CsvMode::Combined => {
+                writer.write_record([
+                    "Type",
+                    "Name",
+                    "Soft",
+                    "Hard",
+                    "Count",
+                    "Soft Impact",
+                    "Hard Impact",
+                    "Well-Known Vendors",
+                ])?;
+                for summary in &summaries {
+                    writer.write_record([
+                        "keyword".to_string(),
+                        summary.keyword.clone(),
+                        summary.soft_count.to_string(),
+                        summary.hard_count.to_string(),
+                        summary.total_count().to_string(),
+                        summary.soft_impact.as_str().to_string(),
+                        summary.hard_impact.as_str().to_string(),
+                        summary.well_known_vendors.join(", "),
+                    ])?;
+                }
+                if !summaries.is_empty() {
+                    let (soft_total, hard_total) = self.total_counts();
+                    writer.write_record([
+                        "total".to_string(),
+                        "TOTAL".to_string(),
+                        soft_total.to_string(),
+                        hard_total.to_string(),
+                        (soft_total + hard_total).to_string(),
+                        String::new(),
+                        String::new(),
+                        String::new(),
+                    ])?;
+                }
+                for label in &entry.labels {
+                    writer.write_record([
+                        "label".to_string(),
+                        label.label.to_string(),
+                        String::new(),
+                        String::new(),
+                        label.result.count.to_string(),
+                        String::new(),
+                        label.impact.to_string(),
+                        Self::vendor_names(&label.result.well_known_vendors),
+                    ])?;
+                }
+            }
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn vendor_names(vendors: &BTreeSet<Vendor>) -> String {
+        vendors
+            .iter()
+            .map(|v| v.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Joins vendor names for a table cell, or `-` when there are none.
+    fn vendor_list(vendors: &[String]) -> String {
+        if vendors.is_empty() {
+            "-".to_string()
+        } else {
+            vendors.join(", ")
+        }
+    }
+
+    pub fn display_table(
+        &self,
+        out: &mut ReportOutput,
+        show_keywords: bool,
+        show_labels: bool,
+        sort: SortOrder,
+        top_n: usize,
+        display: bool,
+    ) -> Result<()> {
+        if self.should_warn_low_file_count() {
+            eprintln!(
+                "\n⚠️  WARNING: Only analyzed {} files from {} packages (less than {} files recommended)",
+                self.total_files, self.total_packages, self.min_file_warning_threshold
+            );
+            eprintln!(
+                "   Consider increasing --max to scan more packages for a comprehensive analysis.\n"
+            );
+        }
+
+        if show_keywords {
+            // Only the display is truncated; the report keeps every keyword.
+            let shown = if top_n == 0 {
+                self.keyword_results.len()
+            } else {
+                top_n
+            };
+            let mut summaries = self.sorted_impact_summary(sort);
+            summaries.truncate(shown);
+
+            if summaries.is_empty() {
+                tracing::info!("No keywords match found in the analyzed packages.");
+                return Ok(());
+            }
+
+            let mut keyboard_rows = Vec::new();
+            for summary in summaries {
+                let well_known_str = if summary.well_known_vendors.is_empty() {
+                    "-".to_string()
+                } else {
+                    let vendors: Vec<_> = summary
+                        .well_known_vendors
+                        .iter()
+                        .map(String::as_str)
+                        .collect();
+                    Self::wrap_list(&vendors, 60)
+                };
+
+                let mut row = vec![
+                    summary.keyword.as_str().cell().bold(true),
+                    summary.soft_count.cell().justify(Justify::Right),
+                    summary.hard_count.cell().justify(Justify::Right),
+                    summary.soft_file_count.cell().justify(Justify::Right),
+                    summary.hard_file_count.cell().justify(Justify::Right),
+                    self.percent_of_files(summary.soft_file_count)
+                        .cell()
+                        .justify(Justify::Right),
+                    self.percent_of_files(summary.hard_file_count)
+                        .cell()
+                        .justify(Justify::Right),
+                    Self::create_impact_cell(summary.soft_impact),
+                    Self::create_impact_cell(summary.hard_impact),
+                    well_known_str.cell(),
+                ];
+
+                if display {
+                    let top_packages: Vec<_> = self.keyword_results[&summary.keyword]
+                        .top_packages(TOP_PACKAGES)
+                        .into_iter()
+                        .map(|p| format!("{} ({})", p.package_name, p.total_count()))
+                        .collect();
+
+                    let top_packages_str = if top_packages.is_empty() {
+                        "-".to_string()
+                    } else {
+                        top_packages.join("\n")
+                    };
+
+                    row.push(top_packages_str.cell());
+                }
+
+                keyboard_rows.push(row);
+            }
+
+            // Bold, so the footer stands apart from the keyword rows. File counts are
+            // not summed, since a file can match several keywords.
+            let (soft_total, hard_total) = self.total_counts();
+            let mut total_row = vec![
+                "TOTAL".cell().bold(true),
+                soft_total.cell().justify(Justify::Right).bold(true),
+                hard_total.cell().justify(Justify::Right).bold(true),
+            ];
+            total_row.extend((0..7).map(|_| "-".cell()));
+            if display {
+                total_row.push("-".cell());
+            }
+            keyboard_rows.push(total_row);
+
+            let mut title = vec![
+                "Keyword".cell().bold(true),
+                "Soft".cell().bold(true),
+                "Hard".cell().bold(true),
+                "Soft Files".cell().bold(true),
+                "Hard Files".cell().bold(true),
+                "Soft % Files".cell().bold(true),
+                "Hard % Files".cell().bold(true),
+                "Soft Impact".cell().bold(true),
+                "Hard Impact".cell().bold(true),
+                "Well-Known Vendors".cell().bold(true),
+            ];
+
+            if display {
+                title.push("Top Packages".cell().bold(true));
+            }
+
+            let table = keyboard_rows.table().title(title);
+
+            out.print_table(table)?;
+            if shown < self.keyword_results.len() {
+                writeln!(
+                    out,
+                    "Showing the top {} of {} keywords.",
+                    shown,
+                    self.keyword_results.len()
+                )?;
+            }
+            writeln!(
+                out,
+                "Analyzed {} files from {} packages.",
+                self.total_files, self.total_packages
+            )?;
+            if self.parse_error_files > 0 {
+                writeln!(
+                    out,
+                    "Note: {} files had parse errors and may have incomplete results.",
+                    self.parse_error_files
+                )?;
+            }
+            if self.non_utf8_files > 0 {
+                writeln!(
+                    out,
+                    "Note: {} files were not valid UTF-8, so matches spanning invalid bytes may be missing.",
+                    self.non_utf8_files
+                )?;
+            }
+
+            if display {
+                self.display_match_locations(out, sort)?;
+            }
+        }
+
+        if show_labels {
+            if show_keywords {
+                writeln!(out)?;
+            }
+
+            let mut label_data: Vec<_> = self.label_results.iter().collect();
+            if label_data.is_empty() {
+                tracing::info!("No labels match found in the analyzed packages.");
+                return Ok(());
+            }
 
             label_data.sort_by(|a, b| a.0.cmp(&b.0));
 
@@ -352,13 +1889,12 @@ impl AnalysisReport {
                 let well_known_str = if result.well_known_vendors.is_empty() {
                     "-".to_string()
                 } else {
-                    let mut vendors: Vec<_> = result
+                    let vendors: Vec<_> = result
                         .well_known_vendors
                         .iter()
-                        .map(|v| v.as_str().trim_end_matches('/'))
+                        .map(|v| v.as_str())
                         .collect();
-                    vendors.sort();
-                    Self::wrap_text(&vendors.join(", "), 60)
+                    Self::wrap_list(&vendors, 60)
                 };
 
                 label_rows.push(vec![
@@ -374,7 +1910,880 @@ impl AnalysisReport {
                 "Well-Known Vendors".cell().bold(true),
             ]);
 
-            let _ = print_stdout(label_table);
+            out.print_table(label_table)?;
+        }
+
+        Ok(())
+    }
+
+    fn display_match_locations(&self, out: &mut ReportOutput, sort: SortOrder) -> Result<()> {
+        for (keyword, result) in self.sorted_keyword_results(sort) {
+            if result.locations.is_empty() {
+                continue;
+            }
+
+            let mut locations: Vec<_> = result.locations.iter().collect();
+            locations.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+
+            writeln!(out)?;
+            writeln!(out, "Matches for {}:", keyword)?;
+            for location in locations {
+                writeln!(
+                    out,
+                    "  {}:{} ({}, {})",
+                    location.file,
+                    location.line,
+                    location.kind.as_str(),
+                    if location.is_hard { "hard" } else { "soft" }
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn display_vendor_report(&self, out: &mut ReportOutput, vendor: &Vendor) -> Result<()> {
+        let mut vendor_data: Vec<_> = self
+            .keyword_results_for_vendor(vendor)
+            .into_iter()
+            .collect();
+        vendor_data.sort_by(|a, b| b.1.1.cmp(&a.1.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut vendor_rows = Vec::new();
+        for (keyword, (soft_count, hard_count)) in vendor_data {
+            vendor_rows.push(vec![
+                keyword.cell().bold(true),
+                soft_count.cell().justify(Justify::Right),
+                hard_count.cell().justify(Justify::Right),
+            ]);
+        }
+
+        writeln!(out)?;
+        writeln!(out, "Exposure for {}:", vendor.as_str())?;
+
+        let vendor_table = vendor_rows.table().title(vec![
+            "Keyword".cell().bold(true),
+            "Soft".cell().bold(true),
+            "Hard".cell().bold(true),
+        ]);
+
+        out.print_table(vendor_table)
+    }
+
+    /// Prints the packages matching `keyword` grouped by their own impact, most
+    /// severe first.
+    pub fn display_drill_down(&self, out: &mut ReportOutput, keyword: &str) -> Result<()> {
+        let packages_by_impact = self.packages_by_impact(keyword);
+        if packages_by_impact.is_empty() {
+            tracing::info!("No package matches {:?}.", keyword);
+            return Ok(());
+        }
+
+        let mut drill_down_rows = Vec::new();
+        for (impact, packages) in packages_by_impact.iter().rev() {
+            let more = format!(
+                "and {} more",
+                packages.len().saturating_sub(DRILL_DOWN_PACKAGES)
+            );
+            let mut names = packages
+                .iter()
+                .take(DRILL_DOWN_PACKAGES)
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            if packages.len() > DRILL_DOWN_PACKAGES {
+                names.push(&more);
+            }
+
+            drill_down_rows.push(vec![
+                Self::create_impact_cell(*impact),
+                packages.len().cell().justify(Justify::Right),
+                Self::wrap_list(&names, 60).cell(),
+            ]);
+        }
+
+        writeln!(out)?;
+        writeln!(out, "Packages using {:?} by their own impact:", keyword)?;
+        out.print_table(drill_down_rows.table().title(vec![
+            "Impact".cell().bold(true),
+            "Count".cell().bold(true),
+            "Packages".cell().bold(true),
+        ]))
+    }
+
+    /// Prints a table of the `n` packages with the most matches of each keyword, keywords
+    /// in `sort` order. Keywords without matches are left out.
+    pub fn display_top_packages(
+        &self,
+        out: &mut ReportOutput,
+        n: usize,
+        sort: SortOrder,
+    ) -> Result<()> {
+        for (keyword, _) in self.sorted_keyword_results(sort) {
+            let top_packages = self.top_packages_by_keyword(keyword, n);
+            if top_packages.is_empty() {
+                continue;
+            }
+
+            let package_rows: Vec<_> = top_packages
+                .into_iter()
+                .map(|(package_name, soft_count, hard_count)| {
+                    vec![
+                        package_name.cell(),
+                        soft_count.cell().justify(Justify::Right),
+                        hard_count.cell().justify(Justify::Right),
+                        (soft_count + hard_count).cell().justify(Justify::Right),
+                    ]
+                })
+                .collect();
+
+            writeln!(out)?;
+            writeln!(out, "Top packages using {:?}:", keyword)?;
+            out.print_table(package_rows.table().title(vec![
+                "Package".cell().bold(true),
+                "Soft".cell().bold(true),
+                "Hard".cell().bold(true),
+                "Total".cell().bold(true),
+            ]))?;
+        }
+
+        Ok(())
+    }
+
+    /// Prints each keyword's soft and hard counts per vendor, keywords in `sort` order
+    /// and vendors by hard count within each keyword.
+    pub fn display_vendor_breakdown(&self, out: &mut ReportOutput, sort: SortOrder) -> Result<()> {
+        let mut breakdown_rows = Vec::new();
+        for (keyword, result) in self.sorted_keyword_results(sort) {
+            let mut vendor_counts: Vec<_> = result.vendor_counts.iter().collect();
+            vendor_counts.sort_by(|a, b| b.1.1.cmp(&a.1.1).then_with(|| a.0.cmp(b.0)));
+
+            for (vendor, (soft_count, hard_count)) in vendor_counts {
+                let vendor_name = match vendor {
+                    Vendor::Other => "other",
+                    vendor => vendor.as_str(),
+                };
+
+                breakdown_rows.push(vec![
+                    keyword.cell().bold(true),
+                    vendor_name.cell(),
+                    soft_count.cell().justify(Justify::Right),
+                    hard_count.cell().justify(Justify::Right),
+                ]);
+            }
+        }
+
+        if breakdown_rows.is_empty() {
+            tracing::info!("No vendor matches any keyword.");
+            return Ok(());
+        }
+
+        let breakdown_table = breakdown_rows.table().title(vec![
+            "Keyword".cell().bold(true),
+            "Vendor".cell().bold(true),
+            "Soft".cell().bold(true),
+            "Hard".cell().bold(true),
+        ]);
+
+        writeln!(out)?;
+        writeln!(out, "Breakdown by vendor:")?;
+        out.print_table(breakdown_table)
+    }
+
+    /// Prints a bar chart per keyword of how many packages use it how many times, to
+    /// tell keywords used heavily by a few packages from ones used a little everywhere.
+    pub fn display_histograms(&self, out: &mut ReportOutput, sort: SortOrder) -> Result<()> {
+        for (keyword, _) in self.sorted_keyword_results(sort) {
+            let histogram = self.symbol_histogram(keyword, HISTOGRAM_BUCKETS);
+            let max_packages = histogram
+                .iter()
+                .map(|(_, packages)| *packages)
+                .max()
+                .unwrap_or_default();
+
+            writeln!(out)?;
+            writeln!(out, "Packages by number of uses of {:?}:", keyword)?;
+            for (range, packages) in histogram {
+                let label = match range.end {
+                    usize::MAX => format!("{}+", range.start),
+                    end if end == range.start + 1 => range.start.to_string(),
+                    end => format!("{}-{}", range.start, end - 1),
+                };
+                let bar = match max_packages {
+                    0 => 0,
+                    max => (packages * HISTOGRAM_WIDTH).div_ceil(max),
+                };
+
+                writeln!(
+                    out,
+                    "  {:>8} | {:<width$} {}",
+                    label,
+                    "#".repeat(bar),
+                    packages,
+                    width = HISTOGRAM_WIDTH
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints one `RESULT key=value ...` line per keyword, in `sort` order, for shell
+    /// scripts to `grep` and `awk` without parsing a table.
+    pub fn display_summary(&self, out: &mut ReportOutput, sort: SortOrder) -> Result<()> {
+        writeln!(out)?;
+        for (keyword, result) in self.sorted_keyword_results(sort) {
+            writeln!(
+                out,
+                "RESULT keyword={} soft={} hard={} soft_impact={} hard_impact={} total_files={}",
+                keyword,
+                result.soft_count,
+                result.hard_count,
+                result.soft_impact(&self.thresholds).as_str().to_lowercase(),
+                result.hard_impact(&self.thresholds).as_str().to_lowercase(),
+                self.total_files
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn display_correlation_report(&self, out: &mut ReportOutput) -> Result<()> {
+        let matrix = self.vendor_keyword_matrix();
+        if matrix.is_empty() {
+            tracing::info!("No well-known vendors match any keyword.");
+            return Ok(());
+        }
+
+        let mut keywords: Vec<_> = self.keyword_results.keys().collect();
+        keywords.sort();
+
+        let mut vendors: Vec<_> = matrix.keys().cloned().collect();
+        vendors.sort();
+
+        let mut correlation_rows = Vec::new();
+        for vendor in vendors {
+            let mut row = vec![vendor.as_str().cell().bold(true)];
+            for keyword in &keywords {
+                let cell = match matrix[&vendor].get(keyword.as_str()) {
+                    Some((_, hard)) if *hard > 0 => "H",
+                    Some((soft, _)) if *soft > 0 => "S",
+                    _ => "-",
+                };
+
+                row.push(cell.cell().justify(Justify::Center));
+            }
+
+            correlation_rows.push(row);
+        }
+
+        let mut title = vec!["Vendor".cell().bold(true)];
+        title.extend(keywords.iter().map(|keyword| keyword.cell().bold(true)));
+
+        writeln!(out)?;
+        out.print_table(correlation_rows.table().title(title))
+    }
+
+    /// Prints how many files use each pair of keywords, as a symmetric keyword × keyword table.
+    pub fn display_cooccurrence_report(&self, out: &mut ReportOutput) -> Result<()> {
+        let matrix = self.cooccurrence_matrix();
+        if matrix.is_empty() {
+            tracing::info!("No file uses more than one keyword.");
+            return Ok(());
+        }
+
+        let mut keywords: Vec<_> = self.keyword_results.keys().collect();
+        keywords.sort();
+
+        let mut cooccurrence_rows = Vec::new();
+        for a in &keywords {
+            let mut row = vec![a.cell().bold(true)];
+            for b in &keywords {
+                let pair = if a < b {
+                    ((*a).clone(), (*b).clone())
+                } else {
+                    ((*b).clone(), (*a).clone())
+                };
+
+                let cell = if a == b {
+                    "-".to_string()
+                } else {
+                    matrix.get(&pair).copied().unwrap_or_default().to_string()
+                };
+
+                row.push(cell.cell().justify(Justify::Right));
+            }
+
+            cooccurrence_rows.push(row);
+        }
+
+        let mut title = vec!["Keyword".cell().bold(true)];
+        title.extend(keywords.iter().map(|keyword| keyword.cell().bold(true)));
+
+        writeln!(out)?;
+        writeln!(out, "Files using both keywords:")?;
+        out.print_table(cooccurrence_rows.table().title(title))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeywordDiff {
+    pub soft_delta: isize,
+    pub hard_delta: isize,
+    /// Well-known vendors affected now but not in the baseline.
+    pub added_vendors: BTreeSet<Vendor>,
+    /// Well-known vendors affected in the baseline but not anymore.
+    pub removed_vendors: BTreeSet<Vendor>,
+}
+
+/// The change of every keyword's results between a baseline report and a newer one.
+#[derive(Debug)]
+pub struct ReportDiff {
+    pub total_files_delta: isize,
+    pub keyword_diffs: HashMap<String, KeywordDiff>,
+}
+
+impl ReportDiff {
+    fn create_delta_cell(delta: isize) -> cli_table::CellStruct {
+        let color = match delta {
+            0 => None,
+            1.. => Some(cli_table::Color::Red),
+            _ => Some(cli_table::Color::Green),
+        };
+
+        format!("{:+}", delta)
+            .cell()
+            .justify(Justify::Right)
+            .foreground_color(color)
+    }
+
+    fn vendor_list(vendors: &BTreeSet<Vendor>) -> String {
+        if vendors.is_empty() {
+            "-".to_string()
+        } else {
+            AnalysisReport::vendor_names(vendors)
+        }
+    }
+
+    pub fn display_table(&self, out: &mut ReportOutput) -> Result<()> {
+        let mut keyword_data: Vec<_> = self.keyword_diffs.iter().collect();
+        keyword_data.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut diff_rows = Vec::new();
+        for (keyword, diff) in keyword_data {
+            diff_rows.push(vec![
+                keyword.cell().bold(true),
+                Self::create_delta_cell(diff.soft_delta),
+                Self::create_delta_cell(diff.hard_delta),
+                Self::vendor_list(&diff.added_vendors).cell(),
+                Self::vendor_list(&diff.removed_vendors).cell(),
+            ]);
+        }
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "Compared to baseline ({:+} files):",
+            self.total_files_delta
+        )?;
+
+        let diff_table = diff_rows.table().title(vec![
+            "Keyword".cell().bold(true),
+            "Soft Δ".cell().bold(true),
+            "Hard Δ".cell().bold(true),
+            "New Vendors".cell().bold(true),
+            "Removed Vendors".cell().bold(true),
+        ]);
+
+        out.print_table(diff_table)
+    }
+}
+
+#[derive(Debug)]
+pub struct SegregatedAnalysisReport {
+    pub production_results: AnalysisReport,
+    pub test_results: AnalysisReport,
+}
+
+impl SegregatedAnalysisReport {
+    pub fn render(&self, out: &mut ReportOutput, options: &RenderOptions) -> Result<()> {
+        match options.format {
+            OutputFormat::Table => self.display_table(
+                out,
+                options.show_keywords,
+                options.show_labels,
+                options.sort,
+                options.top_n,
+                options.display,
+            )?,
+            OutputFormat::Markdown => {
+                writeln!(out, "### Production\n")?;
+                write!(out, "{}", self.production_results.to_markdown(options.sort))?;
+                writeln!(out, "\n### Tests\n")?;
+                write!(out, "{}", self.test_results.to_markdown(options.sort))?;
+            }
+            OutputFormat::Json => {
+                writeln!(out, "{}", self.to_json(options.display, options.sort)?)?
+            }
+            OutputFormat::Csv => {
+                anyhow::bail!("CSV output is not supported for segregated reports")
+            }
+            OutputFormat::Html => {
+                anyhow::bail!("HTML output is not supported for segregated reports")
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_json(&self, display: bool, sort: SortOrder) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&serde_json::json!({
+            "production": self.production_results.to_entry(display, sort),
+            "tests": self.test_results.to_entry(display, sort),
+        }))?)
+    }
+
+    pub fn display_table(
+        &self,
+        out: &mut ReportOutput,
+        show_keywords: bool,
+        show_labels: bool,
+        sort: SortOrder,
+        top_n: usize,
+        display: bool,
+    ) -> Result<()> {
+        writeln!(
+            out,
+            "Production ({} files):",
+            self.production_results.total_files
+        )?;
+        self.production_results.display_table(
+            out,
+            show_keywords,
+            show_labels,
+            sort,
+            top_n,
+            display,
+        )?;
+
+        writeln!(out)?;
+        writeln!(out, "Tests ({} files):", self.test_results.total_files)?;
+        self.test_results
+            .display_table(out, show_keywords, show_labels, sort, top_n, display)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Formats `time` as `YYYY-MM-DD HH:MM:SS UTC`.
+fn format_utc(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, seconds) = ((seconds / 86_400) as i64, seconds % 86_400);
+
+    // Converts days since 1970-01-01 to a civil date, see
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = era * 400 + year_of_era + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn keyword_match(keyword: &str, package: &str, file: &str, is_hard: bool) -> KeywordMatch {
+        KeywordMatch {
+            keyword: keyword.to_string(),
+            vendor: Vendor::from_package(package, &[]),
+            package: Some(package.to_string()),
+            file: file.to_string(),
+            line: 1,
+            kind: MatchKind::FunctionCall,
+            is_hard,
+            offset: 0,
+        }
+    }
+
+    fn sorted_packages(result: &KeywordResult) -> Vec<(String, usize, usize)> {
+        let mut packages: Vec<_> = result
+            .packages
+            .iter()
+            .map(|p| (p.package_name.clone(), p.soft_count, p.hard_count))
+            .collect();
+        packages.sort();
+        packages
+    }
+
+    #[test]
+    fn vendor_prefixes_round_trip() {
+        for vendor in [
+            Vendor::Cakephp,
+            Vendor::Codeigniter4,
+            Vendor::Doctrine,
+            Vendor::Illuminate,
+            Vendor::Laminas,
+            Vendor::Laravel,
+            Vendor::Nette,
+            Vendor::Phalcon,
+            Vendor::Phpunit,
+            Vendor::Spiral,
+            Vendor::Symfony,
+            Vendor::Twig,
+            Vendor::Typo3,
+            Vendor::Yiisoft,
+            Vendor::Zendframework,
+        ] {
+            let package = format!("{}/some-package", vendor.as_str());
+
+            assert_eq!(Vendor::from_package(&package, &[]), vendor, "{}", package);
+            assert!(vendor.is_well_known());
         }
     }
+
+    #[test]
+    fn vendor_names_have_no_trailing_slash() {
+        let vendor = Vendor::from_package("symfony/console", &[]);
+
+        assert_eq!(vendor, Vendor::Symfony);
+        assert_eq!(vendor.as_str(), "symfony");
+        assert_eq!(
+            AnalysisReport::vendor_names(&BTreeSet::from([Vendor::Symfony, Vendor::Laravel])),
+            "laravel, symfony"
+        );
+    }
+
+    #[test]
+    fn lists_are_wrapped_between_items() {
+        assert_eq!(AnalysisReport::wrap_list(&[], 10), "");
+        assert_eq!(AnalysisReport::wrap_list(&[""], 10), "");
+
+        assert_eq!(
+            AnalysisReport::wrap_list(&["symfony", "laravel"], 16),
+            "symfony, laravel"
+        );
+        assert_eq!(
+            AnalysisReport::wrap_list(&["symfony", "laravel"], 15),
+            "symfony\nlaravel"
+        );
+
+        assert_eq!(
+            AnalysisReport::wrap_list(&["symfony", "laravel", "doctrine", "phpunit", "twig"], 20),
+            "symfony, laravel\ndoctrine, phpunit\ntwig"
+        );
+    }
+
+    #[test]
+    fn items_longer_than_a_line_are_split() {
+        assert_eq!(
+            AnalysisReport::wrap_list(&["abcdefghijklmnopqrstuvwxyz"], 10),
+            "abcdefghij\nklmnopqrst\nuvwxyz"
+        );
+        assert_eq!(
+            AnalysisReport::wrap_list(&["twig", "a-long-vendor/package", "nette"], 10),
+            "twig\na-long-ven\ndor/packag\ne, nette"
+        );
+        // Widths are counted in characters, not bytes.
+        assert_eq!(
+            AnalysisReport::wrap_list(&["zürich", "genève"], 14),
+            "zürich, genève"
+        );
+    }
+
+    #[test]
+    fn unknown_and_custom_vendors() {
+        let custom_vendors: Vec<CustomVendor> = vec![("acme/".into(), "Acme Corp".into())];
+
+        assert_eq!(Vendor::from_package("acme/tool", &[]), Vendor::Other);
+        assert!(!Vendor::Other.is_well_known());
+        // A vendor name that merely starts like a well-known one is not that vendor.
+        assert_eq!(Vendor::from_package("symfonyx/tool", &[]), Vendor::Other);
+
+        let vendor = Vendor::from_package("acme/tool", &custom_vendors);
+        assert_eq!(vendor, Vendor::Custom("Acme Corp".into()));
+        assert_eq!(vendor.as_str(), "Acme Corp");
+        assert!(vendor.is_well_known());
+    }
+
+    /// Builds a report from `(keyword, file, is_hard)` indexes, as generated by proptest.
+    fn report_of(matches: &[(usize, usize, bool)]) -> AnalysisReport {
+        let packages = ["symfony/console", "laravel/framework", "acme/tool"];
+        let mut report = AnalysisReport::new(matches.len(), BTreeSet::new());
+        report.add_keyword_matches(
+            matches
+                .iter()
+                .map(|&(keyword, file, is_hard)| {
+                    let package = packages[file % packages.len()];
+                    keyword_match(
+                        ["let", "scope", "using"][keyword],
+                        package,
+                        &format!("{}/File{}.php", package, file),
+                        is_hard,
+                    )
+                })
+                .collect(),
+        );
+
+        report
+    }
+
+    proptest! {
+        #[test]
+        fn merge_is_commutative_for_total_counts(
+            left in prop::collection::vec((0..3usize, 0..8usize, any::<bool>()), 0..40),
+            right in prop::collection::vec((0..3usize, 0..8usize, any::<bool>()), 0..40),
+        ) {
+            let mut left_first = report_of(&left);
+            left_first.merge(report_of(&right));
+            let mut right_first = report_of(&right);
+            right_first.merge(report_of(&left));
+
+            prop_assert_eq!(left_first.total_counts(), right_first.total_counts());
+            prop_assert_eq!(left_first.total_files, right_first.total_files);
+            prop_assert_eq!(left_first.impact_summary(), right_first.impact_summary());
+        }
+    }
+
+    #[test]
+    fn parallel_and_sequential_matches_give_the_same_results() {
+        let packages = ["symfony/console", "laravel/framework", "acme/tool"];
+        let matches: Vec<KeywordMatch> = (0..5_000)
+            .map(|i| {
+                let package = packages[i % packages.len()];
+                keyword_match(
+                    ["let", "scope", "using"][i % 3],
+                    package,
+                    &format!("{}/src/File{}.php", package, i % 97),
+                    i % 7 == 0,
+                )
+            })
+            .collect();
+
+        let mut parallel = AnalysisReport::new(100, BTreeSet::new());
+        parallel.set_keep_locations(true);
+        parallel.add_keyword_matches(matches.clone());
+
+        let mut sequential = AnalysisReport::new(100, BTreeSet::new());
+        sequential.set_keep_locations(true);
+        for m in &matches {
+            sequential.add_keyword_match(m);
+        }
+
+        assert_eq!(parallel.impact_summary(), sequential.impact_summary());
+        for (keyword, result) in &sequential.keyword_results {
+            let other = &parallel.keyword_results[keyword];
+            assert_eq!(other.vendor_counts, result.vendor_counts);
+            assert_eq!(sorted_packages(other), sorted_packages(result));
+            assert_eq!(other.locations.len(), result.locations.len());
+        }
+    }
+
+    #[test]
+    fn locations_are_only_kept_on_request() {
+        let matches = vec![
+            keyword_match("let", "acme/a", "acme/a/A.php", true),
+            keyword_match("let", "acme/a", "acme/a/A.php", false),
+            keyword_match("let", "acme/a", "acme/a/B.php", false),
+        ];
+
+        let mut report = AnalysisReport::new(2, BTreeSet::new());
+        report.add_keyword_matches(matches.clone());
+        assert!(report.keyword_results["let"].locations.is_empty());
+        assert!(report.matched_files().is_empty());
+
+        let mut report = AnalysisReport::new(2, BTreeSet::new());
+        report.set_keep_locations(true);
+        for m in &matches {
+            report.add_keyword_match(m);
+        }
+        let locations = &report.keyword_results["let"].locations;
+        assert_eq!(locations.len(), 3);
+        // Consecutive locations of a file share its name.
+        assert!(Arc::ptr_eq(&locations[0].file, &locations[1].file));
+        assert_eq!(&*locations[2].file, "acme/a/B.php");
+    }
+
+    #[test]
+    fn csv_and_json_follow_the_sort_order() {
+        let mut report = AnalysisReport::new(4, BTreeSet::new());
+        report.add_keyword_matches(vec![
+            keyword_match("zeta", "acme/a", "acme/a/A.php", true),
+            keyword_match("zeta", "acme/a", "acme/a/B.php", true),
+            keyword_match("alpha", "acme/a", "acme/a/A.php", false),
+        ]);
+
+        let keyword_order = |sort| {
+            let mut csv = Vec::new();
+            report
+                .to_csv_writer(&mut csv, CsvMode::KeywordsOnly, sort)
+                .unwrap();
+            let csv = String::from_utf8(csv).unwrap();
+            let csv_keywords: Vec<String> = csv
+                .lines()
+                .skip(1)
+                .map(|line| line.split(',').next().unwrap().to_string())
+                .filter(|keyword| keyword != "TOTAL")
+                .collect();
+
+            let json: serde_json::Value =
+                serde_json::from_str(&report.to_json(false, sort).unwrap()).unwrap();
+            let json_keywords: Vec<String> = json["keywords"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|entry| entry["keyword"].as_str().unwrap().to_string())
+                .collect();
+
+            assert_eq!(csv_keywords, json_keywords);
+            csv_keywords
+        };
+
+        assert_eq!(keyword_order(SortOrder::Name), ["alpha", "zeta"]);
+        assert_eq!(keyword_order(SortOrder::TotalCount), ["zeta", "alpha"]);
+    }
+
+    #[test]
+    fn examples_come_from_different_vendors_and_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("a.php");
+        let second = dir.path().join("b.php");
+        let third = dir.path().join("c.php");
+        fs::write(&first, "<?php\nlet();\nlet(2);\n").unwrap();
+        fs::write(&second, "<?php\n\n    $x->let();\n").unwrap();
+        fs::write(&third, "<?php\nfunction let() {}\n").unwrap();
+        let path = |file: &Path| file.to_string_lossy().into_owned();
+
+        let mut report = AnalysisReport::new(3, BTreeSet::new());
+        report.set_keep_locations(true);
+        report.add_keyword_matches(vec![
+            KeywordMatch {
+                line: 2,
+                ..keyword_match("let", "symfony/console", &path(&first), false)
+            },
+            KeywordMatch {
+                line: 3,
+                ..keyword_match("let", "symfony/console", &path(&first), false)
+            },
+            KeywordMatch {
+                line: 2,
+                ..keyword_match("let", "symfony/console", &path(&third), false)
+            },
+            KeywordMatch {
+                line: 3,
+                ..keyword_match("let", "laravel/framework", &path(&second), false)
+            },
+        ]);
+
+        let examples = report.collect_examples("let", 5);
+        let snippets: Vec<&str> = examples.iter().map(|e| e.snippet.as_str()).collect();
+        // One example per vendor first, then one per remaining file.
+        assert_eq!(snippets, ["let();", "$x->let();", "function let() {}"]);
+        assert_eq!(examples[1].file, path(&second));
+        assert_eq!(examples[1].line, 3);
+
+        assert_eq!(report.collect_examples("let", 1).len(), 1);
+        assert!(report.collect_examples("scope", 5).is_empty());
+        assert!(report.to_html().contains("<pre>$x-&gt;let();</pre>"));
+    }
+
+    #[test]
+    fn matched_files_are_listed_once_in_path_order() {
+        let mut report = AnalysisReport::new(3, BTreeSet::new());
+        report.set_keep_locations(true);
+        report.add_keyword_matches(vec![
+            keyword_match("let", "acme/b", "acme/b/src/My File.php", true),
+            keyword_match("scope", "acme/b", "acme/b/src/My File.php", false),
+            keyword_match("let", "acme/a", "acme/a/src/A.php", false),
+        ]);
+
+        assert_eq!(
+            report.matched_files().into_iter().collect::<Vec<_>>(),
+            ["acme/a/src/A.php", "acme/b/src/My File.php"]
+        );
+    }
+
+    #[test]
+    fn impact_levels_start_at_the_default_thresholds() {
+        let thresholds = ImpactThresholds::default();
+        let levels: Vec<_> = [0, 1, 25, 26, 100, 101, 500, 501]
+            .into_iter()
+            .map(|total| ImpactLevel::calculate(total, &thresholds))
+            .collect();
+
+        assert_eq!(
+            levels,
+            [
+                ImpactLevel::None,
+                ImpactLevel::Low,
+                ImpactLevel::Low,
+                ImpactLevel::Medium,
+                ImpactLevel::Medium,
+                ImpactLevel::High,
+                ImpactLevel::High,
+                ImpactLevel::Critical,
+            ]
+        );
+    }
+
+    #[test]
+    fn hard_impact_counts_soft_and_hard_matches() {
+        let thresholds = ImpactThresholds::default();
+        let mut result = KeywordResult::new();
+        result.soft_count = 25;
+        result.hard_count = 1;
+
+        assert_eq!(result.soft_impact(&thresholds), ImpactLevel::Low);
+        assert_eq!(result.hard_impact(&thresholds), ImpactLevel::Medium);
+
+        result.soft_count = 0;
+        assert_eq!(result.soft_impact(&thresholds), ImpactLevel::None);
+        assert_eq!(result.hard_impact(&thresholds), ImpactLevel::Low);
+    }
+
+    #[test]
+    fn total_count_saturates_instead_of_overflowing() {
+        let mut result = KeywordResult::new();
+        result.soft_count = usize::MAX / 2;
+        result.hard_count = usize::MAX / 2 + 1;
+        assert_eq!(result.total_count(), usize::MAX);
+
+        result.hard_count = usize::MAX;
+        assert_eq!(result.total_count(), usize::MAX);
+        assert_eq!(
+            result.hard_impact(&ImpactThresholds::default()),
+            ImpactLevel::Critical
+        );
+    }
+
+    #[test]
+    fn low_file_count_warning_stops_at_the_threshold() {
+        assert!(AnalysisReport::new(199_999, BTreeSet::new()).should_warn_low_file_count());
+        assert!(!AnalysisReport::new(200_000, BTreeSet::new()).should_warn_low_file_count());
+    }
 }
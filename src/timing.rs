@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use cli_table::Cell;
+use cli_table::Style;
+use cli_table::Table;
+use cli_table::format::Justify;
+use tracing::Subscriber;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span::Attributes;
+use tracing::span::Id;
+use tracing::span::Record;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::output::ReportOutput;
+
+const SLOWEST_PACKAGES: usize = 10;
+
+/// The spans timed by [`TimingLayer`], and the phase each belongs to.
+const TIMED_SPANS: &[(&str, Phase)] = &[
+    ("downloading-package", Phase::Download),
+    ("extracting-zip", Phase::Extraction),
+    ("extracting-tarball", Phase::Extraction),
+    ("analyzing-file", Phase::Analysis),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Download,
+    Extraction,
+    Analysis,
+}
+
+impl Phase {
+    const ALL: [Phase; 3] = [Phase::Download, Phase::Extraction, Phase::Analysis];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Phase::Download => "Download",
+            Phase::Extraction => "Extraction",
+            Phase::Analysis => "Analysis",
+        }
+    }
+}
+
+/// The durations of every timed span, grouped by phase, with the package the
+/// span worked on when it records a `package` field.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    spans: Arc<Mutex<HashMap<Phase, Vec<(Option<String>, Duration)>>>>,
+}
+
+impl Timings {
+    /// Returns a layer adding the duration of every timed span to these timings.
+    pub fn layer(&self) -> TimingLayer {
+        TimingLayer {
+            timings: self.clone(),
+        }
+    }
+
+    /// Returns the duration of each package in `phase`, summing the spans of the
+    /// same package (e.g. one per analyzed file). Spans without a package are
+    /// counted on their own.
+    pub fn package_durations(&self, phase: Phase) -> Vec<(Option<String>, Duration)> {
+        let spans = self.spans.lock().unwrap();
+        let Some(spans) = spans.get(&phase) else {
+            return Vec::new();
+        };
+
+        let mut by_package: HashMap<&str, Duration> = HashMap::new();
+        let mut durations = Vec::new();
+        for (package, duration) in spans {
+            match package {
+                Some(package) => *by_package.entry(package).or_default() += *duration,
+                None => durations.push((None, *duration)),
+            }
+        }
+
+        durations.extend(
+            by_package
+                .into_iter()
+                .map(|(package, duration)| (Some(package.to_string()), duration)),
+        );
+        durations.sort_by(|a, b| b.1.cmp(&a.1));
+
+        durations
+    }
+
+    pub fn display(&self, out: &mut ReportOutput) -> Result<()> {
+        for (phase, verb) in [(Phase::Analysis, "analyze"), (Phase::Download, "download")] {
+            let durations = self.package_durations(phase);
+            let slowest_rows: Vec<_> = durations
+                .iter()
+                .filter_map(|(package, duration)| Some((package.as_ref()?, duration)))
+                .take(SLOWEST_PACKAGES)
+                .map(|(package, duration)| {
+                    vec![
+                        package.cell(),
+                        format_duration(*duration).cell().justify(Justify::Right),
+                    ]
+                })
+                .collect();
+
+            if slowest_rows.is_empty() {
+                continue;
+            }
+
+            writeln!(out)?;
+            writeln!(out, "Slowest packages to {}:", verb)?;
+            out.print_table(slowest_rows.table().title(vec![
+                "Package".cell().bold(true),
+                "Duration".cell().bold(true),
+            ]))?;
+        }
+
+        let mut phase_rows = Vec::new();
+        for phase in Phase::ALL {
+            let mut durations: Vec<Duration> = self
+                .package_durations(phase)
+                .into_iter()
+                .map(|(_, duration)| duration)
+                .collect();
+            if durations.is_empty() {
+                continue;
+            }
+
+            durations.sort();
+            let total: Duration = durations.iter().sum();
+            let mean = total / durations.len() as u32;
+
+            phase_rows.push(vec![
+                phase.as_str().cell().bold(true),
+                durations.len().cell().justify(Justify::Right),
+                format_duration(total).cell().justify(Justify::Right),
+                format_duration(mean).cell().justify(Justify::Right),
+                format_duration(percentile(&durations, 50))
+                    .cell()
+                    .justify(Justify::Right),
+                format_duration(percentile(&durations, 99))
+                    .cell()
+                    .justify(Justify::Right),
+            ]);
+        }
+
+        if phase_rows.is_empty() {
+            tracing::info!("No package was timed.");
+            return Ok(());
+        }
+
+        writeln!(out)?;
+        writeln!(out, "Time per package and phase:")?;
+        out.print_table(phase_rows.table().title(vec![
+            "Phase".cell().bold(true),
+            "Packages".cell().bold(true),
+            "Total".cell().bold(true),
+            "Mean".cell().bold(true),
+            "Median".cell().bold(true),
+            "P99".cell().bold(true),
+        ]))
+    }
+}
+
+/// Times the spans listed in [`TIMED_SPANS`] from creation to close, which
+/// includes the time an async span spends waiting.
+///
+/// The layer is only installed with `--timing`, so other runs pay nothing for it.
+#[derive(Debug)]
+pub struct TimingLayer {
+    timings: Timings,
+}
+
+struct SpanTiming {
+    phase: Phase,
+    started: Instant,
+    package: Option<String>,
+}
+
+impl<S> Layer<S> for TimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let name = attrs.metadata().name();
+        let Some(&(_, phase)) = TIMED_SPANS.iter().find(|(span, _)| *span == name) else {
+            return;
+        };
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut timing = SpanTiming {
+            phase,
+            started: Instant::now(),
+            package: None,
+        };
+        attrs.record(&mut PackageVisitor(&mut timing.package));
+
+        span.extensions_mut().insert(timing);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+            values.record(&mut PackageVisitor(&mut timing.package));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(timing) = span.extensions_mut().remove::<SpanTiming>() else {
+            return;
+        };
+
+        self.timings
+            .spans
+            .lock()
+            .unwrap()
+            .entry(timing.phase)
+            .or_default()
+            .push((timing.package, timing.started.elapsed()));
+    }
+}
+
+struct PackageVisitor<'a>(&'a mut Option<String>);
+
+impl Visit for PackageVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "package" {
+            *self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "package" {
+            *self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+/// Returns the `p`th percentile of sorted, non-empty `durations`.
+fn percentile(durations: &[Duration], p: usize) -> Duration {
+    let index = (durations.len() * p).div_ceil(100).saturating_sub(1);
+
+    durations[index.min(durations.len() - 1)]
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}s", duration.as_secs_f64())
+}
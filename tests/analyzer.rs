@@ -0,0 +1,217 @@
+//! Runs the analyzer on the PHP files in `tests/fixtures`.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use keyword_impact_analyzer::analyzer::AnalysisOptions;
+use keyword_impact_analyzer::analyzer::DirectoryAnalysis;
+use keyword_impact_analyzer::analyzer::analyze_directory;
+use keyword_impact_analyzer::analyzer::analyze_directory_streaming;
+use keyword_impact_analyzer::analyzer::analyze_string;
+use keyword_impact_analyzer::results::KeywordMatch;
+use keyword_impact_analyzer::results::MatchKind;
+use keyword_impact_analyzer::results::Vendor;
+
+fn keyword_matches(source: &str, keywords: &[&str]) -> Vec<KeywordMatch> {
+    keyword_matches_with(source, keywords, AnalysisOptions::default())
+}
+
+fn keyword_matches_with(
+    source: &str,
+    keywords: &[&str],
+    options: AnalysisOptions,
+) -> Vec<KeywordMatch> {
+    let keywords: Vec<String> = keywords.iter().map(|keyword| keyword.to_string()).collect();
+
+    analyze_string(source.to_string(), &keywords, &[], options).keyword_matches
+}
+
+/// The keyword, kind and hardness of each match, in source order.
+fn summarize(matches: &[KeywordMatch]) -> Vec<(&str, MatchKind, bool)> {
+    matches
+        .iter()
+        .map(|m| (m.keyword.as_str(), m.kind, m.is_hard))
+        .collect()
+}
+
+#[test]
+fn match_arm_constants_are_hard_matches() {
+    let matches = keyword_matches(
+        include_str!("fixtures/match_arm_constants.php"),
+        &["let", "scope", "using"],
+    );
+
+    // Class, local, fully qualified and own constants match, but strings do not.
+    assert_eq!(
+        summarize(&matches),
+        [
+            ("let", MatchKind::ClassConstantAccess, true),
+            ("scope", MatchKind::ClassConstantAccess, true),
+            ("let", MatchKind::Identifier, true),
+            ("let", MatchKind::Identifier, true),
+            ("scope", MatchKind::Identifier, true),
+            ("using", MatchKind::ClassConstantAccess, true),
+        ]
+    );
+}
+
+#[test]
+fn method_names_are_soft_matches_only() {
+    let matches = keyword_matches(include_str!("fixtures/methods.php"), &["let", "scope"]);
+
+    assert_eq!(
+        summarize(&matches),
+        [
+            ("let", MatchKind::MethodName, false),
+            ("scope", MatchKind::MethodName, false),
+            ("let", MatchKind::MethodName, false),
+            ("let", MatchKind::MethodCall, false),
+            ("scope", MatchKind::MethodName, false),
+        ]
+    );
+}
+
+#[test]
+fn enum_cases_are_hard_matches() {
+    let matches = keyword_matches(
+        include_str!("fixtures/enum_cases.php"),
+        &["let", "using", "scope"],
+    );
+
+    assert_eq!(
+        summarize(&matches),
+        [
+            ("let", MatchKind::EnumCaseName, true),
+            ("using", MatchKind::EnumCaseName, true),
+            ("scope", MatchKind::EnumCaseName, true),
+            // The case of the backed enum.
+            ("let", MatchKind::EnumCaseName, true),
+            ("let", MatchKind::ClassConstantAccess, true),
+        ]
+    );
+}
+
+#[test]
+fn class_constant_declarations_and_accesses_are_hard_matches() {
+    let matches = keyword_matches(
+        include_str!("fixtures/class_constants.php"),
+        &["let", "scope", "using"],
+    );
+
+    assert_eq!(
+        summarize(&matches),
+        [
+            ("let", MatchKind::ConstantName, true),
+            ("scope", MatchKind::ClassConstantName, true),
+            ("let", MatchKind::ClassConstantName, true),
+            ("using", MatchKind::ClassConstantName, true),
+            ("let", MatchKind::ClassConstantAccess, true),
+            ("using", MatchKind::ClassConstantAccess, true),
+            ("scope", MatchKind::ClassConstantAccess, true),
+            ("let", MatchKind::ClassConstantAccess, true),
+            ("scope", MatchKind::ClassConstantAccess, true),
+        ]
+    );
+}
+
+#[test]
+fn named_arguments_are_soft_matches_only() {
+    let matches = keyword_matches(
+        include_str!("fixtures/named_arguments.php"),
+        &["let", "scope"],
+    );
+
+    assert_eq!(
+        summarize(&matches),
+        [
+            ("let", MatchKind::NamedArgument, false),
+            ("scope", MatchKind::NamedArgument, false),
+            ("let", MatchKind::NamedArgument, false),
+            ("let", MatchKind::NamedArgument, false),
+            ("let", MatchKind::NamedArgument, false),
+            ("let", MatchKind::NamedArgument, false),
+        ]
+    );
+}
+
+#[test]
+fn closure_use_variables_and_arrow_function_parameters_are_soft_matches() {
+    let source = include_str!("fixtures/closure_variables.php");
+    let options = AnalysisOptions {
+        include_variables: true,
+        ..AnalysisOptions::default()
+    };
+
+    let matches = keyword_matches_with(source, &["let", "scope"], options);
+
+    assert_eq!(
+        summarize(&matches),
+        [
+            ("let", MatchKind::Variable, false),
+            ("scope", MatchKind::Variable, false),
+            ("let", MatchKind::ClosureUseVariable, false),
+            ("scope", MatchKind::ClosureUseVariable, false),
+            ("let", MatchKind::Variable, false),
+            ("scope", MatchKind::Variable, false),
+            ("let", MatchKind::ArrowFunctionParameter, false),
+            ("let", MatchKind::Variable, false),
+        ]
+    );
+    assert!(keyword_matches(source, &["let", "scope"]).is_empty());
+}
+
+#[test]
+fn directory_matches_are_counted_per_keyword_and_package() {
+    let sources = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sources");
+
+    let keywords = vec!["let".to_string(), "scope".to_string()];
+
+    let report = analyze_directory(DirectoryAnalysis::new(sources, keywords)).unwrap();
+
+    assert_eq!(report.total_files, 2);
+    assert_eq!(report.total_packages, 2);
+
+    let scope = &report.keyword_results["scope"];
+    assert_eq!((scope.soft_count, scope.hard_count), (0, 1));
+    assert_eq!(scope.file_count, 1);
+    assert_eq!(scope.packages.len(), 1);
+    assert_eq!(scope.packages[0].package_name, "acme/tool");
+
+    let r#let = &report.keyword_results["let"];
+    assert_eq!((r#let.soft_count, r#let.hard_count), (2, 0));
+    assert_eq!(r#let.soft_file_count, 2);
+    assert_eq!(r#let.packages.len(), 2);
+    assert!(r#let.well_known_vendors.contains(&Vendor::Symfony));
+}
+
+#[test]
+fn streamed_directory_matches_are_passed_per_file() {
+    let sources = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sources");
+
+    let keywords = vec!["let".to_string(), "scope".to_string()];
+
+    let streamed = Mutex::new(Vec::new());
+    let total_files =
+        analyze_directory_streaming(DirectoryAnalysis::new(sources, keywords), |matches| {
+            streamed.lock().unwrap().extend(matches)
+        })
+        .unwrap();
+
+    assert_eq!(total_files, 2);
+
+    let mut streamed: Vec<(String, bool)> = streamed
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|m| (m.keyword, m.is_hard))
+        .collect();
+    streamed.sort();
+    assert_eq!(
+        streamed,
+        [
+            ("let".to_string(), false),
+            ("let".to_string(), false),
+            ("scope".to_string(), true),
+        ]
+    );
+}
@@ -0,0 +1,88 @@
+//! Extracts archives laid out like downloaded ones, under `zipballs/<vendor>/<package>`.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use keyword_impact_analyzer::extractor::extract_packages;
+
+fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+    let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+    for (name, contents) in entries {
+        zip.start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+fn write_tarball(path: &Path, entries: &[(&str, &str)]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+    let encoder = GzEncoder::new(fs::File::create(path).unwrap(), Compression::default());
+    let mut tarball = tar::Builder::new(encoder);
+    for (name, contents) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tarball
+            .append_data(&mut header, name, contents.as_bytes())
+            .unwrap();
+    }
+    tarball.into_inner().unwrap().finish().unwrap();
+}
+
+#[test]
+fn downloaded_archives_are_extracted_to_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    let zipballs = dir.path().join("zipballs");
+    write_zip(
+        &zipballs.join("acme/tool/acme-tool.zip"),
+        &[
+            ("acme-tool-1a2b3c/composer.json", r#"{"name": "acme/tool"}"#),
+            ("acme-tool-1a2b3c/src/Tool.php", "<?php"),
+        ],
+    );
+    write_tarball(
+        &zipballs.join("symfony/console/symfony-console.tar.gz"),
+        &[("console-7.0.0/src/Command.php", "<?php")],
+    );
+    // Not an archive, so it is ignored rather than counted as a failure.
+    fs::write(zipballs.join("acme/tool/notes.txt"), "downloaded").unwrap();
+
+    assert_eq!(extract_packages(dir.path().to_path_buf()).unwrap(), 2);
+
+    let sources = dir.path().join("sources");
+    assert!(sources.join("acme/tool/composer.json").is_file());
+    assert!(sources.join("acme/tool/src/Tool.php").is_file());
+    assert!(sources.join("symfony/console/src/Command.php").is_file());
+
+    // Already extracted packages are skipped, and still count as extracted.
+    fs::remove_file(sources.join("acme/tool/src/Tool.php")).unwrap();
+    assert_eq!(extract_packages(dir.path().to_path_buf()).unwrap(), 2);
+    assert!(!sources.join("acme/tool/src/Tool.php").exists());
+}
+
+#[test]
+fn corrupt_archives_are_not_counted_as_extracted() {
+    let dir = tempfile::tempdir().unwrap();
+    let zipballs = dir.path().join("zipballs");
+    write_zip(
+        &zipballs.join("acme/tool/acme-tool.zip"),
+        &[("acme-tool/src/Tool.php", "<?php")],
+    );
+    fs::create_dir_all(zipballs.join("acme/broken")).unwrap();
+    fs::write(zipballs.join("acme/broken/acme-broken.zip"), "not a zip").unwrap();
+
+    assert_eq!(extract_packages(dir.path().to_path_buf()).unwrap(), 1);
+
+    let sources = dir.path().join("sources");
+    assert!(sources.join("acme/tool/src/Tool.php").is_file());
+    assert!(!sources.join("acme/broken").exists());
+    assert!(!sources.join("acme/broken.tmp").exists());
+}
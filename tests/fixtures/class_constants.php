@@ -0,0 +1,28 @@
+<?php
+
+namespace App;
+
+const LET = 'not a class constant';
+
+interface HasScope
+{
+    public const SCOPE = 'scope';
+}
+
+final class Config implements HasScope
+{
+    const LET = 'let';
+    private const USING = 'using', OTHER = 'other';
+
+    public function values(Config $config): array
+    {
+        return [self::LET, static::USING, $config::SCOPE, Config::{$this->name()}];
+    }
+
+    private function name(): string
+    {
+        return 'OTHER';
+    }
+}
+
+echo Config::LET, HasScope::SCOPE;
@@ -0,0 +1,10 @@
+<?php
+
+$let = 1;
+$scope = 2;
+
+$closure = function (int $value) use ($let, &$scope): int {
+    return $value + $let + $scope;
+};
+
+$arrow = fn(int $let, int $other) => $let * $other;
@@ -0,0 +1,17 @@
+<?php
+
+enum Status
+{
+    case Let;
+    case Using;
+    case Scope;
+    case Other;
+}
+
+enum Suit: string
+{
+    case Let = 'let';
+    case Hearts = 'hearts';
+}
+
+$status = Status::Let;
@@ -0,0 +1,19 @@
+<?php
+
+namespace App;
+
+final class Describer
+{
+    public function describe(Status $status): string
+    {
+        return match ($status) {
+            Status::LET, Status::SCOPE => 'class constants',
+            LET => 'global constant',
+            \LET => 'fully qualified constant',
+            'let', 'LET' => 'strings',
+            SCOPE => 'global constant',
+            self::USING => 'own constant',
+            default => 'other',
+        };
+    }
+}
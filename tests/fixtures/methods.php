@@ -0,0 +1,26 @@
+<?php
+
+namespace App;
+
+interface Binding
+{
+    public function let(string $name): void;
+}
+
+abstract class AbstractBinding implements Binding
+{
+    abstract protected function scope(): array;
+}
+
+final class ConcreteBinding extends AbstractBinding
+{
+    public function let(string $name): void
+    {
+        $this->let($name);
+    }
+
+    protected function scope(): array
+    {
+        return [];
+    }
+}
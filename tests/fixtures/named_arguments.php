@@ -0,0 +1,12 @@
+<?php
+
+function bind(string $let = '', string $scope = ''): void
+{
+}
+
+bind(let: 'function call');
+bind(scope: 'several', let: 'arguments');
+bind(...['let' => 'unpacked array']);
+(new Binder())->bind(let: 'method call');
+Binder::create(let: 'static method call');
+new Binder(let: 'constructor');
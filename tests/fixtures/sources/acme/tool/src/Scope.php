@@ -0,0 +1,10 @@
+<?php
+
+namespace Acme\Tool;
+
+final class Scope
+{
+    public function let(): void
+    {
+    }
+}
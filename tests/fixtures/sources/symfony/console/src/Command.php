@@ -0,0 +1,11 @@
+<?php
+
+namespace Symfony\Component\Console;
+
+class Command
+{
+    public function run(Runner $runner): void
+    {
+        $runner->let();
+    }
+}